@@ -0,0 +1,422 @@
+use std::borrow::Cow;
+use std::cmp;
+use std::convert::Infallible;
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use aoc_core::{Answer, Solution};
+use aoc_core::lint::{Check, Lint};
+
+use crate::vocabulary::Vocabulary;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Digit,
+    Word,
+}
+
+// Part 1 only reads digits, part 2 reads spelled out digits as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Digits,
+    DigitsAndWords,
+}
+
+// What to do with lines without any digit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Policy {
+    // Stop at the first one, the puzzle promises there are none.
+    Strict,
+    // Leave them out of the total and report them along with it.
+    Lenient,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    Sensitive,
+    Insensitive,
+}
+
+// A digit of a line, spelled out or not, at the bytes `span` of the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub value: u32,
+    pub span: Range<usize>,
+    pub source: Source,
+}
+
+// The first and the last digit of a line. Matches may overlap, so that
+// words like "eightwo" count as 8 from the start and as 2 from the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Calibration {
+    pub first: Match,
+    pub last: Match,
+}
+
+impl Calibration {
+    pub fn value(&self) -> u32 {
+        self.first.value * 10 + self.last.value
+    }
+}
+
+// A line of a document without any digit, 1-based.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoDigit {
+    pub line: usize,
+}
+
+impl fmt::Display for NoDigit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "no digit found at line {}", self.line)
+    }
+}
+
+impl std::error::Error for NoDigit {}
+
+// The sum of the calibration values of a document, and the lines left out of
+// it with `Policy::Lenient`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Total {
+    pub sum: u64,
+    pub skipped: Vec<NoDigit>,
+}
+
+impl Total {
+    // Adds the calibration of a line of a document, following `policy` when
+    // it has no digit.
+    pub fn add(&mut self, calibration: Result<Calibration, NoDigit>, policy: Policy) -> Result<(), NoDigit> {
+        match (calibration, policy) {
+            (Ok(calibration), _) => self.sum += calibration.value() as u64,
+            (Err(no_digit), Policy::Strict) => return Err(no_digit),
+            (Err(no_digit), Policy::Lenient) => self.skipped.push(no_digit),
+        }
+
+        Ok(())
+    }
+}
+
+// Finds the digits of a line, and the words of a vocabulary in
+// `Mode::DigitsAndWords`, with a single automaton going over the line once.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    automaton: AhoCorasick,
+    // The value and source of every pattern of the automaton, by pattern id.
+    patterns: Vec<(u32, Source)>,
+    case: Case,
+}
+
+impl Decoder {
+    pub fn new(vocabulary: &Vocabulary, mode: Mode, case: Case) -> Self {
+        let mut patterns: Vec<(String, u32, Source)> = (0..10).map(|value| (value.to_string(), value, Source::Digit)).collect();
+        if mode == Mode::DigitsAndWords {
+            for (word, value) in vocabulary.words() {
+                let word = match case {
+                    Case::Sensitive => word.clone(),
+                    Case::Insensitive => word.to_lowercase(),
+                };
+                patterns.push((word, *value, Source::Word));
+            }
+        }
+
+        // Overlapping searches need the standard match semantics, which report
+        // every pattern found at any position.
+        let automaton = AhoCorasickBuilder::new()
+            .match_kind(MatchKind::Standard)
+            .ascii_case_insensitive(case == Case::Insensitive)
+            .build(patterns.iter().map(|(pattern, _, _)| pattern))
+            .expect("digits and words make a valid automaton");
+
+        Self { automaton, patterns: patterns.into_iter().map(|(_, value, source)| (value, source)).collect(), case }
+    }
+
+    // The decoders of the puzzle, built once.
+    pub fn english(mode: Mode) -> &'static Self {
+        static DIGITS: OnceLock<Decoder> = OnceLock::new();
+        static DIGITS_AND_WORDS: OnceLock<Decoder> = OnceLock::new();
+
+        let decoder = match mode {
+            Mode::Digits => &DIGITS,
+            Mode::DigitsAndWords => &DIGITS_AND_WORDS,
+        };
+        decoder.get_or_init(|| Decoder::new(&Vocabulary::english(), mode, Case::Sensitive))
+    }
+
+    // None for lines without any digit.
+    pub fn decode(&self, line: &str) -> Option<Calibration> {
+        let (haystack, offsets) = self.haystack(line);
+        let original = |offset: usize| offsets.as_ref().map_or(offset, |offsets| offsets[offset]);
+
+        let matches = self.automaton.find_overlapping_iter(haystack.as_ref()).map(|found| {
+            let (value, source) = self.patterns[found.pattern().as_usize()];
+            Match { value, span: original(found.start())..original(found.end()), source }
+        });
+
+        // Of the matches starting at the same byte, the longest one wins.
+        let (first, last) = matches.fold(None, |bounds: Option<(Match, Match)>, m| match bounds {
+            None => Some((m.clone(), m)),
+            Some((first, last)) => Some((
+                cmp::min_by_key(first, m.clone(), |m| (m.span.start, cmp::Reverse(m.span.end))),
+                cmp::max_by_key(last, m, |m| (m.span.start, m.span.end)),
+            )),
+        })?;
+
+        Some(Calibration { first, last })
+    }
+
+    // The line to search, along with the byte offset in `line` of every byte of
+    // it and of its end if it isn't `line` itself. The automaton ignores the
+    // case of ASCII letters on its own, other letters are lowercased first.
+    fn haystack<'a>(&self, line: &'a str) -> (Cow<'a, str>, Option<Vec<usize>>) {
+        if self.case == Case::Sensitive || line.is_ascii() {
+            return (Cow::Borrowed(line), None);
+        }
+
+        let mut lowercase = String::with_capacity(line.len());
+        let mut offsets = Vec::with_capacity(line.len() + 1);
+        for (offset, c) in line.char_indices() {
+            let start = lowercase.len();
+            lowercase.extend(c.to_lowercase());
+            offsets.extend(std::iter::repeat_n(offset, lowercase.len() - start));
+        }
+        offsets.push(line.len());
+
+        (Cow::Owned(lowercase), Some(offsets))
+    }
+
+    pub fn calibration_value(&self, line: &str) -> Option<u32> {
+        self.decode(line).map(|calibration| calibration.value())
+    }
+
+    // The calibration of every line of `document`, in order.
+    pub fn decode_lines<'a>(&'a self, document: &'a str) -> impl Iterator<Item = Result<Calibration, NoDigit>> + 'a {
+        document
+            .lines()
+            .enumerate()
+            .map(|(i, line)| self.decode(line).ok_or(NoDigit { line: i + 1 }))
+    }
+
+    pub fn sum_of_calibration_values(&self, document: &str, policy: Policy) -> Result<Total, NoDigit> {
+        let mut total = Total::default();
+        for calibration in self.decode_lines(document) {
+            total.add(calibration, policy)?;
+        }
+
+        Ok(total)
+    }
+}
+
+// None for lines without any digit.
+pub fn decode(line: &str, mode: Mode) -> Option<Calibration> {
+    Decoder::english(mode).decode(line)
+}
+
+pub fn calibration_value(line: &str, mode: Mode) -> Option<u32> {
+    Decoder::english(mode).calibration_value(line)
+}
+
+pub fn sum_of_calibration_values(document: &str, mode: Mode, policy: Policy) -> Result<Total, NoDigit> {
+    Decoder::english(mode).sum_of_calibration_values(document, policy)
+}
+
+pub struct Document {
+    text: String,
+}
+
+impl Solution for Document {
+    type Error = Infallible;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Ok(Self { text: input.to_string() })
+    }
+
+    // A line without digits leaves the part unsolved, `lint` tells which.
+    fn part1(&mut self) -> Answer {
+        sum_of_calibration_values(&self.text, Mode::Digits, Policy::Strict).map_or(Answer::Unsolved, |total| total.sum.into())
+    }
+
+    fn part2(&mut self) -> Answer {
+        sum_of_calibration_values(&self.text, Mode::DigitsAndWords, Policy::Strict).map_or(Answer::Unsolved, |total| total.sum.into())
+    }
+}
+
+impl Lint for Document {
+    fn lint(&self) -> Vec<Check> {
+        let violation = |mode| sum_of_calibration_values(&self.text, mode, Policy::Strict).err().map(|no_digit| no_digit.to_string());
+
+        vec![
+            Check::new("every line has a digit", violation(Mode::Digits)),
+            Check::new("every line has a digit, spelled out or not", violation(Mode::DigitsAndWords)),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(
+            Some(Calibration {
+                first: Match { value: 2, span: 0..3, source: Source::Word },
+                last: Match { value: 9, span: 4..8, source: Source::Word },
+            }),
+            decode("two1nine", Mode::DigitsAndWords),
+        );
+        assert_eq!(
+            Some(Calibration {
+                first: Match { value: 4, span: 0..1, source: Source::Digit },
+                last: Match { value: 2, span: 15..16, source: Source::Digit },
+            }),
+            decode("4nineeightseven2", Mode::DigitsAndWords),
+        );
+        assert_eq!(None, decode("abcdef", Mode::DigitsAndWords));
+        assert_eq!(None, decode("", Mode::DigitsAndWords));
+    }
+
+    #[test]
+    fn test_decode_overlapping_words() {
+        let calibration = decode("eightwo", Mode::DigitsAndWords).unwrap();
+
+        assert_eq!(Match { value: 8, span: 0..5, source: Source::Word }, calibration.first);
+        assert_eq!(Match { value: 2, span: 4..7, source: Source::Word }, calibration.last);
+        assert_eq!(82, calibration.value());
+
+        assert_eq!(Some(18), calibration_value("oneight", Mode::DigitsAndWords));
+        assert_eq!(Some(79), calibration_value("sevenine", Mode::DigitsAndWords));
+        assert_eq!(Some(11), calibration_value("xoneabc", Mode::DigitsAndWords));
+    }
+
+    #[test]
+    fn test_decode_non_ascii() {
+        let calibration = decode("éone→7", Mode::DigitsAndWords).unwrap();
+
+        assert_eq!(2..5, calibration.first.span);
+        assert_eq!(8..9, calibration.last.span);
+        assert_eq!(17, calibration.value());
+    }
+
+    #[test]
+    fn test_digits_mode() {
+        assert_eq!(
+            Some(Calibration {
+                first: Match { value: 1, span: 3..4, source: Source::Digit },
+                last: Match { value: 2, span: 8..9, source: Source::Digit },
+            }),
+            decode("two1nine2", Mode::Digits),
+        );
+        assert_eq!(None, decode("eightwothree", Mode::Digits));
+        assert_eq!(Some(77), calibration_value("treb7uchet", Mode::Digits));
+    }
+
+    #[test]
+    fn test_decoder_with_zero() {
+        let decoder = Decoder::new(&Vocabulary::english_with_zero(), Mode::DigitsAndWords, Case::Sensitive);
+
+        assert_eq!(Some(10), decoder.calibration_value("onezero"));
+        assert_eq!(Some(0), decoder.calibration_value("zer0"));
+        assert_eq!(Some(11), calibration_value("onezero", Mode::DigitsAndWords));
+    }
+
+    #[test]
+    fn test_decoder_case_insensitive() {
+        let decoder = Decoder::new(&Vocabulary::english(), Mode::DigitsAndWords, Case::Insensitive);
+
+        assert_eq!(Some(29), decoder.calibration_value("TWO1Nine"));
+        assert_eq!(Some(82), decoder.calibration_value("EighTwo"));
+        assert_eq!(None, decode("TWO", Mode::DigitsAndWords));
+
+        let vocabulary = "fünf 5\nacht 8".parse::<Vocabulary>().unwrap();
+        let decoder = Decoder::new(&vocabulary, Mode::DigitsAndWords, Case::Insensitive);
+        let calibration = decoder.decode("xFÜNFyACHT").unwrap();
+
+        assert_eq!(Match { value: 5, span: 1..6, source: Source::Word }, calibration.first);
+        assert_eq!(Match { value: 8, span: 7..11, source: Source::Word }, calibration.last);
+    }
+
+    #[test]
+    fn test_decoder_prefers_longest_word() {
+        let vocabulary = "un 1\nune 1\nneuf 9".parse::<Vocabulary>().unwrap();
+        let decoder = Decoder::new(&vocabulary, Mode::DigitsAndWords, Case::Sensitive);
+        let calibration = decoder.decode("uneuf").unwrap();
+
+        assert_eq!(Match { value: 1, span: 0..3, source: Source::Word }, calibration.first);
+        assert_eq!(Match { value: 9, span: 1..5, source: Source::Word }, calibration.last);
+    }
+
+    #[test]
+    fn test_decoder_bundled_vocabulary() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("vocabularies/german.txt");
+        let vocabulary = std::fs::read_to_string(path).unwrap().parse::<Vocabulary>().unwrap();
+        let decoder = Decoder::new(&vocabulary, Mode::DigitsAndWords, Case::Insensitive);
+
+        assert_eq!(
+            29 + 83 + 13 + 24 + 42 + 14 + 76,
+            decoder.sum_of_calibration_values("\
+zwei1neun
+achtzweidrei
+abceins2dreixyz
+xzweins3vier
+4neunachtsieben2
+zeinsacht234
+7pqrstsechszehn", Policy::Strict).unwrap().sum,
+        );
+        assert_eq!(Some(0), decoder.calibration_value("Null"));
+    }
+
+    const PART_1_EXAMPLE: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
+
+    const PART_2_EXAMPLE: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
+
+    #[test]
+    fn test_sum_of_calibration_values() {
+        let total = |document, mode| sum_of_calibration_values(document, mode, Policy::Strict).map(|total| total.sum);
+
+        assert_eq!(Ok(142), total(PART_1_EXAMPLE, Mode::Digits));
+        assert_eq!(Ok(281), total(PART_2_EXAMPLE, Mode::DigitsAndWords));
+
+        // Words make no difference without them.
+        assert_eq!(Ok(142), total(PART_1_EXAMPLE, Mode::DigitsAndWords));
+    }
+
+    #[test]
+    fn test_lines_without_digits() {
+        assert_eq!(Err(NoDigit { line: 2 }), sum_of_calibration_values(PART_2_EXAMPLE, Mode::Digits, Policy::Strict));
+        assert_eq!(
+            Ok(Total { sum: 29 + 24 + 42 + 76, skipped: vec![NoDigit { line: 2 }, NoDigit { line: 3 }] }),
+            sum_of_calibration_values("two1nine\nabcxyz\n\nxtwone3four\n4nineeightseven2\n7pqrstsixteen", Mode::DigitsAndWords, Policy::Lenient),
+        );
+        assert_eq!("no digit found at line 2", NoDigit { line: 2 }.to_string());
+
+        let lines = Decoder::english(Mode::Digits).decode_lines("a1\nb\n").map(|calibration| calibration.map(|c| c.value())).collect::<Vec<_>>();
+        assert_eq!(vec![Ok(11), Err(NoDigit { line: 2 })], lines);
+    }
+
+    #[test]
+    fn test_document() {
+        assert_eq!(Answer::Number(142), Document::parse(PART_1_EXAMPLE).unwrap().part1());
+        assert_eq!(Answer::Number(281), Document::parse(PART_2_EXAMPLE).unwrap().part2());
+        assert_eq!(Answer::Unsolved, Document::parse(PART_2_EXAMPLE).unwrap().part1());
+    }
+
+    #[test]
+    fn test_document_lint() {
+        assert!(Document::parse(PART_1_EXAMPLE).unwrap().lint().iter().all(Check::holds));
+
+        let checks = Document::parse(PART_2_EXAMPLE).unwrap().lint();
+        assert_eq!(Some("no digit found at line 2".to_string()), checks[0].violation);
+        assert!(checks[1].holds());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod calibration;
+pub mod stream;
+pub mod vocabulary;
@@ -1,39 +1,97 @@
-use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::PathBuf;
+use aoc_core::input::{self, Source};
+use aoc_core::parse;
+use day_1::calibration::{Case, Decoder, Mode, NoDigit, Policy, Total};
+use day_1::stream;
+use day_1::vocabulary::Vocabulary;
 
-fn main() {
-    let digit_words: HashMap<&str, &str> = [
-        ("one", "1"),
-        ("two", "2"),
-        ("three", "3"),
-        ("four", "4"),
-        ("five", "5"),
-        ("six", "6"),
-        ("seven", "7"),
-        ("eight", "8"),
-        ("nine", "9"),
-    ].iter().cloned().collect();
-
-    let sum = std::fs::read_to_string("input.txt")
-        .unwrap()
-        .lines()
-        .map(|line| {
-            let mut line = line.to_string();
-
-            for (word, digit) in digit_words.iter() {
-                line = line.replace(word, format!("{}{}{}", word, digit, word).as_str());
+// Lines without digits were left out of the sums with `--lenient`, and stop
+// the part they are found in otherwise. Tells whether the part has a total.
+fn print_total(part: u8, label: &str, total: &Result<Total, NoDigit>) -> bool {
+    match total {
+        Ok(total) => {
+            for no_digit in &total.skipped {
+                eprintln!("day 1: part {}: {}, skipped", part, no_digit);
             }
+            println!("{}: {}", label, total.sum);
+            true
+        }
+        Err(no_digit) => {
+            eprintln!("day 1: part {}: {}", part, no_digit);
+            false
+        }
+    }
+}
 
-            let digits = line.chars()
-                .filter(|c| c.is_digit(10))
-                .map(|c| c.to_digit(10).unwrap())
-                .collect::<Vec<u32>>();
+// Removes `flag` from the arguments, telling whether it was there.
+fn take_flag(args: &mut Vec<String>, flag: &str) -> bool {
+    let position = args.iter().position(|arg| arg == flag);
+    position.map(|i| args.remove(i)).is_some()
+}
 
-            let first = digits[0];
-            let last = digits[digits.len() - 1];
+fn path_of(source: Source) -> Option<PathBuf> {
+    match source {
+        Source::File(path) => Some(path),
+        Source::Lookup { dir, day } => Some(input::lookup_path(&dir, day)),
+        Source::Stdin => None,
+    }
+}
 
-            first * 10 + last
-        })
-        .sum::<u32>();
+fn main() {
+    let mut args = std::env::args().skip(1).collect::<Vec<_>>();
+    // Fails a part on its first line without digits, unless `--lenient` is passed.
+    let policy = if take_flag(&mut args, "--lenient") { Policy::Lenient } else { Policy::Strict };
+    // Reads the document a line at a time rather than all at once.
+    let streaming = take_flag(&mut args, "--stream");
+    // Maps the document in memory and decodes chunks of it on every core.
+    let parallel = take_flag(&mut args, "--parallel");
+    if streaming && parallel {
+        eprintln!("day 1: --stream and --parallel cannot be used together");
+        std::process::exit(1);
+    }
+
+    // Matches spelled out digits regardless of case, as in "Nine" or "FÜNF".
+    let case = if take_flag(&mut args, "--ignore-case") { Case::Insensitive } else { Case::Sensitive };
+    // Spells out 0 as "zero" too. Vocabulary files list their own zero, if any.
+    let zero = take_flag(&mut args, "--zero");
+
+    // An optional second argument names a file of spelled out digits in
+    // another language, see vocabularies/.
+    let vocabulary = match args.get(1) {
+        Some(_) if zero => parse::or_exit(1, Err("--zero only applies to the English words")),
+        Some(path) => {
+            let text = parse::or_exit(1, std::fs::read_to_string(path).map_err(|err| format!("could not read {}: {}", path, err)));
+            parse::or_exit(1, Vocabulary::parse(&text, case))
+        }
+        None if zero => Vocabulary::english_with_zero(),
+        None => Vocabulary::english(),
+    };
+    let words = Decoder::new(&vocabulary, Mode::DigitsAndWords, case);
+    let decoders = [Decoder::english(Mode::Digits), &words];
+
+    let source = Source::from_arg(args.first().map(String::as_str), 1);
+    let parts = if parallel {
+        let path = parse::or_exit(1, path_of(source).ok_or("--parallel needs a file, not stdin"));
+        parse::or_exit(1, stream::sums_of_file(&path, &decoders, policy))
+    } else if streaming {
+        let parts = match path_of(source) {
+            Some(path) => {
+                let file = parse::or_exit(1, File::open(&path).map_err(|err| format!("could not read {}: {}", path.display(), err)));
+                stream::sums_from_reader(BufReader::new(file), &decoders, policy)
+            }
+            None => stream::sums_from_reader(io::stdin().lock(), &decoders, policy),
+        };
+        parse::or_exit(1, parts)
+    } else {
+        let document = parse::or_exit(1, source.read());
+        decoders.iter().map(|decoder| decoder.sum_of_calibration_values(&document, policy)).collect()
+    };
 
-    println!("{}", sum);
+    let part1 = print_total(1, "Sum of calibration values", &parts[0]);
+    let part2 = print_total(2, "Sum of calibration values with spelled out digits", &parts[1]);
+    if !(part1 && part2) {
+        std::process::exit(1);
+    }
 }
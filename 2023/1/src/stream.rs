@@ -0,0 +1,285 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::str;
+use memmap2::Mmap;
+use rayon::prelude::*;
+
+use crate::calibration::{Decoder, NoDigit, Policy, Total};
+
+// Bytes of a memory-mapped document decoded by each task, up to the end of
+// the line crossing the limit.
+pub const CHUNK_SIZE: usize = 1 << 20;
+
+// Lines without digits only stop the part they are found in, see `Tally`.
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    InvalidUtf8 { line: usize },
+}
+
+impl Error {
+    // Errors found in a chunk of a document are numbered from the start of
+    // the chunk, this moves them after the `lines` lines before it.
+    fn after(self, lines: usize) -> Self {
+        match self {
+            Error::InvalidUtf8 { line } => Error::InvalidUtf8 { line: lines + line },
+            err => err,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "could not read the document: {}", err),
+            Error::InvalidUtf8 { line } => write!(f, "line {} is not valid UTF-8", line),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+// The totals of the lines read so far, one per decoder, so that both parts
+// are decoded in a single pass. With `Policy::Strict` a part stops at its
+// first line without digits while the others go on.
+struct Tally {
+    parts: Vec<Result<Total, NoDigit>>,
+    lines: usize,
+}
+
+impl Tally {
+    fn new(decoders: &[&Decoder]) -> Self {
+        Self { parts: vec![Ok(Total::default()); decoders.len()], lines: 0 }
+    }
+
+    fn add(&mut self, line: &str, decoders: &[&Decoder], policy: Policy) {
+        self.lines += 1;
+        for (decoder, part) in decoders.iter().zip(&mut self.parts) {
+            if let Ok(total) = part {
+                if let Err(no_digit) = total.add(decoder.decode(line).ok_or(NoDigit { line: self.lines }), policy) {
+                    *part = Err(no_digit);
+                }
+            }
+        }
+    }
+
+    // Once every part has stopped, the rest of the document is not read.
+    fn stopped(&self) -> bool {
+        self.parts.iter().all(Result::is_err)
+    }
+}
+
+// Decodes the document line by line, holding a single line in memory. Line
+// endings are the ones of `str::lines`, so sums are the same as in memory.
+pub fn sums_from_reader(mut reader: impl BufRead, decoders: &[&Decoder], policy: Policy) -> Result<Vec<Result<Total, NoDigit>>, Error> {
+    let mut tally = Tally::new(decoders);
+    let mut buffer = Vec::new();
+
+    while !tally.stopped() && reader.read_until(b'\n', &mut buffer)? > 0 {
+        let line = str::from_utf8(&buffer).map_err(|_| Error::InvalidUtf8 { line: tally.lines + 1 })?;
+        let line = line.strip_suffix('\n').map_or(line, |line| line.strip_suffix('\r').unwrap_or(line));
+        tally.add(line, decoders, policy);
+        buffer.clear();
+    }
+
+    Ok(tally.parts)
+}
+
+// `bytes` cut into chunks of whole lines, of at least `chunk_size` bytes but
+// for the last one.
+fn chunks(bytes: &[u8], chunk_size: usize) -> Vec<&[u8]> {
+    let chunk_size = chunk_size.max(1);
+    let mut chunks = Vec::new();
+    let mut rest = bytes;
+
+    while !rest.is_empty() {
+        let end = rest
+            .get(chunk_size - 1..)
+            .and_then(|tail| tail.iter().position(|&b| b == b'\n'))
+            .map_or(rest.len(), |i| chunk_size + i);
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+
+    chunks
+}
+
+// The lines of a chunk up to invalid UTF-8, if any, which is reported unless
+// every part stopped before it, as it is when streaming. Each part goes over
+// the chunk on its own, up to its first line without digits when strict.
+fn tally_chunk(chunk: &[u8], decoders: &[&Decoder], policy: Policy) -> (Tally, Option<Error>) {
+    let (text, valid) = match str::from_utf8(chunk) {
+        Ok(text) => (text, true),
+        Err(err) => {
+            let end = chunk[..err.valid_up_to()].iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+            (str::from_utf8(&chunk[..end]).unwrap(), false)
+        }
+    };
+
+    let parts = decoders.iter().map(|decoder| decoder.sum_of_calibration_values(text, policy)).collect();
+    let tally = Tally { parts, lines: text.lines().count() };
+
+    let error = (!valid && !tally.stopped()).then(|| Error::InvalidUtf8 { line: tally.lines + 1 });
+    (tally, error)
+}
+
+// Decodes chunks of `bytes` in parallel, adding up their totals in order so
+// that skipped lines and errors are the same as when streaming.
+pub fn sums_in_chunks(bytes: &[u8], chunk_size: usize, decoders: &[&Decoder], policy: Policy) -> Result<Vec<Result<Total, NoDigit>>, Error> {
+    let tallies = chunks(bytes, chunk_size)
+        .par_iter()
+        .map(|chunk| tally_chunk(chunk, decoders, policy))
+        .collect::<Vec<_>>();
+
+    let mut tally = Tally::new(decoders);
+    for (chunk_tally, error) in tallies {
+        if tally.stopped() {
+            break;
+        }
+        let lines = tally.lines;
+        for (part, chunk_part) in tally.parts.iter_mut().zip(chunk_tally.parts) {
+            match (part, chunk_part) {
+                (Ok(total), Ok(chunk_total)) => {
+                    total.sum += chunk_total.sum;
+                    total.skipped.extend(chunk_total.skipped.into_iter().map(|NoDigit { line }| NoDigit { line: lines + line }));
+                }
+                (part @ Ok(_), Err(NoDigit { line })) => *part = Err(NoDigit { line: lines + line }),
+                (Err(_), _) => {}
+            }
+        }
+        if let Some(error) = error.filter(|_| !tally.stopped()) {
+            return Err(error.after(lines));
+        }
+        tally.lines += chunk_tally.lines;
+    }
+
+    Ok(tally.parts)
+}
+
+// Maps the file in memory rather than reading it, leaving it to the system to
+// page it in and out.
+pub fn sums_of_file(path: &Path, decoders: &[&Decoder], policy: Policy) -> Result<Vec<Result<Total, NoDigit>>, Error> {
+    let file = File::open(path)?;
+    // Safety: the file must not be truncated while mapped, which holds for
+    // documents nobody writes to while they are decoded.
+    let bytes = unsafe { Mmap::map(&file)? };
+
+    sums_in_chunks(&bytes, CHUNK_SIZE, decoders, policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calibration::{self, Mode};
+
+    const DOCUMENT: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+
+xtwone3four\r
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+nothing here
+1abc2
+";
+
+    fn decoders() -> [&'static Decoder; 2] {
+        [Decoder::english(Mode::Digits), Decoder::english(Mode::DigitsAndWords)]
+    }
+
+    fn in_memory(document: &str, policy: Policy) -> Vec<Result<Total, NoDigit>> {
+        [Mode::Digits, Mode::DigitsAndWords]
+            .into_iter()
+            .map(|mode| calibration::sum_of_calibration_values(document, mode, policy))
+            .collect()
+    }
+
+    #[test]
+    fn test_chunks() {
+        let bytes = b"ab\ncd\nef\ng";
+
+        assert_eq!(vec![&b"ab\n"[..], b"cd\n", b"ef\n", b"g"], chunks(bytes, 1));
+        assert_eq!(vec![&b"ab\ncd\n"[..], b"ef\ng"], chunks(bytes, 4));
+        assert_eq!(vec![&bytes[..]], chunks(bytes, usize::MAX));
+        assert!(chunks(b"", 4).is_empty());
+    }
+
+    #[test]
+    fn test_same_sums_in_every_mode() {
+        let document = DOCUMENT.repeat(50);
+        let expected = in_memory(&document, Policy::Lenient);
+        assert_eq!(Ok(281 + 12), expected[1].as_ref().map(|total| total.sum / 50));
+
+        assert_eq!(expected, sums_from_reader(document.as_bytes(), &decoders(), Policy::Lenient).unwrap());
+        for chunk_size in [1, 7, 100, CHUNK_SIZE] {
+            assert_eq!(expected, sums_in_chunks(document.as_bytes(), chunk_size, &decoders(), Policy::Lenient).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_strict_stops_each_part_at_its_first_line_without_digits() {
+        // Words decode line 2 in part 2, the first line left is further.
+        let document = DOCUMENT.repeat(3);
+        let expected = in_memory(&document, Policy::Strict);
+        assert_eq!(vec![Err(NoDigit { line: 2 }), Err(NoDigit { line: 4 })], expected);
+
+        assert_eq!(expected, sums_from_reader(document.as_bytes(), &decoders(), Policy::Strict).unwrap());
+        for chunk_size in [1, 16, 30, CHUNK_SIZE] {
+            assert_eq!(expected, sums_in_chunks(document.as_bytes(), chunk_size, &decoders(), Policy::Strict).unwrap());
+        }
+
+        // Part 2 is still summed when part 1 stops.
+        let document = "1abc2\ntwo\n";
+        let expected = vec![Err(NoDigit { line: 2 }), Ok(Total { sum: 12 + 22, skipped: Vec::new() })];
+        assert_eq!(expected, in_memory(document, Policy::Strict));
+        assert_eq!(expected, sums_from_reader(document.as_bytes(), &decoders(), Policy::Strict).unwrap());
+        for chunk_size in [1, CHUNK_SIZE] {
+            assert_eq!(expected, sums_in_chunks(document.as_bytes(), chunk_size, &decoders(), Policy::Strict).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let document = b"a1\nb2\n\xff3\nc4\n";
+
+        let error = sums_from_reader(&document[..], &decoders(), Policy::Lenient).unwrap_err();
+        assert_eq!("line 3 is not valid UTF-8", error.to_string());
+        for chunk_size in [1, 4, CHUNK_SIZE] {
+            let error = sums_in_chunks(document, chunk_size, &decoders(), Policy::Lenient).unwrap_err();
+            assert_eq!("line 3 is not valid UTF-8", error.to_string());
+        }
+
+        // Every part stopped before the invalid line, which is never read.
+        let document = b"a\nb2\n\xff3\n";
+        let expected = vec![Err(NoDigit { line: 1 }); 2];
+        assert_eq!(expected, sums_from_reader(&document[..], &decoders(), Policy::Strict).unwrap());
+        for chunk_size in [1, 4, CHUNK_SIZE] {
+            assert_eq!(expected, sums_in_chunks(document, chunk_size, &decoders(), Policy::Strict).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_sums_of_file() {
+        let path = std::env::temp_dir().join(format!("day-1-stream-{}.txt", std::process::id()));
+        std::fs::write(&path, DOCUMENT.trim_end()).unwrap();
+        let sums = sums_of_file(&path, &decoders(), Policy::Lenient);
+        std::fs::write(&path, "").unwrap();
+        let empty = sums_of_file(&path, &decoders(), Policy::Strict);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(in_memory(DOCUMENT, Policy::Lenient), sums.unwrap());
+        assert_eq!(vec![Ok(Total::default()); 2], empty.unwrap());
+        assert!(matches!(sums_of_file(&path, &decoders(), Policy::Lenient), Err(Error::Io(_))));
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::parse;
+
+use crate::calibration::Case;
+
+// The words spelling out digits, in some language.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+const ENGLISH: [&str; 10] = ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+impl Vocabulary {
+    // "one" to "nine", as in the puzzle.
+    pub fn english() -> Self {
+        Self { words: ENGLISH.iter().zip(0..).skip(1).map(|(word, value)| (word.to_string(), value)).collect() }
+    }
+
+    pub fn english_with_zero() -> Self {
+        Self { words: ENGLISH.iter().zip(0..).map(|(word, value)| (word.to_string(), value)).collect() }
+    }
+
+    pub fn words(&self) -> &[(String, u32)] {
+        &self.words
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    MissingValue,
+    InvalidValue(String),
+    UnexpectedToken(String),
+    DigitInWord(String),
+    DuplicateWord(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingValue => write!(f, "expected a word followed by its digit"),
+            ErrorKind::InvalidValue(s) => write!(f, "invalid digit {:?}, expected 0 to 9", s),
+            ErrorKind::UnexpectedToken(s) => write!(f, "unexpected {:?} after the digit", s),
+            ErrorKind::DigitInWord(s) => write!(f, "word {:?} contains a digit", s),
+            ErrorKind::DuplicateWord(s) => write!(f, "word {:?} is already defined", s),
+        }
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+impl Vocabulary {
+    // One `<word> <digit>` per line, like `drei 3`. Blank lines and lines
+    // starting with `#` are skipped. Words matched regardless of case must
+    // differ in more than case, or they would collide.
+    pub fn parse(s: &str, case: Case) -> Result<Self, ParseError> {
+        let same = |a: &str, b: &str| match case {
+            Case::Sensitive => a == b,
+            Case::Insensitive => a.to_lowercase() == b.to_lowercase(),
+        };
+        let mut words: Vec<(String, u32)> = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let error = |token: &str, kind| ParseError::new(i + 1, parse::column(line, token), kind);
+            if line.trim().is_empty() || line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            let mut tokens = line.split_whitespace();
+            let word = tokens.next().unwrap();
+            let value_str = tokens.next().ok_or_else(|| error(&line[line.len()..], ErrorKind::MissingValue))?;
+            if let Some(token) = tokens.next() {
+                return Err(error(token, ErrorKind::UnexpectedToken(token.to_string())));
+            }
+
+            let value = value_str
+                .parse::<u32>()
+                .ok()
+                .filter(|value| *value <= 9)
+                .ok_or_else(|| error(value_str, ErrorKind::InvalidValue(value_str.to_string())))?;
+            if word.chars().any(|c| c.is_ascii_digit()) {
+                return Err(error(word, ErrorKind::DigitInWord(word.to_string())));
+            }
+            if words.iter().any(|(existing, _)| same(existing, word)) {
+                return Err(error(word, ErrorKind::DuplicateWord(word.to_string())));
+            }
+
+            words.push((word.to_string(), value));
+        }
+
+        Ok(Self { words })
+    }
+}
+
+impl FromStr for Vocabulary {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, Case::Sensitive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_english() {
+        assert_eq!(9, Vocabulary::english().words().len());
+        assert_eq!(("one".to_string(), 1), Vocabulary::english().words()[0]);
+        assert_eq!(("zero".to_string(), 0), Vocabulary::english_with_zero().words()[0]);
+    }
+
+    #[test]
+    fn test_vocabulary_from_str() {
+        let vocabulary = "# German\n\neins 1\n  zwei   2\nfünf 5\n".parse::<Vocabulary>().unwrap();
+
+        assert_eq!(
+            vec![("eins".to_string(), 1), ("zwei".to_string(), 2), ("fünf".to_string(), 5)],
+            vocabulary.words(),
+        );
+        assert_eq!(Vocabulary::english(), "one 1\ntwo 2\nthree 3\nfour 4\nfive 5\nsix 6\nseven 7\neight 8\nnine 9".parse().unwrap());
+    }
+
+    #[test]
+    fn test_vocabulary_from_invalid_str() {
+        assert_eq!(Err(ParseError::new(2, 5, ErrorKind::MissingValue)), "un 1\ndeux".parse::<Vocabulary>());
+        assert_eq!(Err(ParseError::new(1, 5, ErrorKind::InvalidValue("10".into()))), "dix 10".parse::<Vocabulary>());
+        assert_eq!(Err(ParseError::new(1, 6, ErrorKind::UnexpectedToken("x".into()))), "un 1 x".parse::<Vocabulary>());
+        assert_eq!(Err(ParseError::new(1, 1, ErrorKind::DigitInWord("x1".into()))), "x1 1".parse::<Vocabulary>());
+        assert_eq!(Err(ParseError::new(2, 1, ErrorKind::DuplicateWord("un".into()))), "un 1\nun 2".parse::<Vocabulary>());
+    }
+
+    #[test]
+    fn test_vocabulary_parse_case_insensitive() {
+        assert!(Vocabulary::parse("Eins 1\neins 1", Case::Sensitive).is_ok());
+        assert_eq!(Err(ParseError::new(2, 1, ErrorKind::DuplicateWord("eins".into()))), Vocabulary::parse("Eins 1\neins 1", Case::Insensitive));
+        assert_eq!(Err(ParseError::new(2, 1, ErrorKind::DuplicateWord("FÜNF".into()))), Vocabulary::parse("fünf 5\nFÜNF 5", Case::Insensitive));
+    }
+
+    #[test]
+    fn test_bundled_vocabularies() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("vocabularies");
+
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            let vocabulary = Vocabulary::parse(&std::fs::read_to_string(&path).unwrap(), Case::Insensitive);
+
+            assert!(vocabulary.is_ok(), "{}: {}", path.display(), vocabulary.unwrap_err());
+        }
+    }
+}
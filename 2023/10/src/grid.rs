@@ -7,6 +7,12 @@ J => (i+1, j-1)   ( 1, -1)
 F => (i-1, j+1)   (-1, +1)
 */
 use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::lint::{Check, Lint};
+use aoc_core::render::{Color, Frame, Render, Style};
+use aoc_core::{Answer, Solution};
+use aoc_grid::{Position, RaggedRow, DIRECTIONS_4};
 
 #[derive(PartialEq, Debug)]
 enum Connection {
@@ -19,7 +25,7 @@ enum Connection {
 }
 
 impl Connection {
-    fn directions(&self) -> [(i32, i32); 2] {
+    fn directions(&self) -> [(isize, isize); 2] {
         match self {
             Connection::NorthSouth => [(1, 0), (-1, 0)],
             Connection::EastWest => [(0, -1), (0, 1)],
@@ -29,6 +35,44 @@ impl Connection {
             Connection::SouthEast => [(0, 1), (1, 0)],
         }
     }
+
+    fn from_directions(directions: [(isize, isize); 2]) -> Option<Self> {
+        [
+            Connection::NorthSouth,
+            Connection::EastWest,
+            Connection::NorthEast,
+            Connection::NorthWest,
+            Connection::SouthWest,
+            Connection::SouthEast,
+        ]
+        .into_iter()
+        .find(|connection| {
+            let [a, b] = connection.directions();
+            directions == [a, b] || directions == [b, a]
+        })
+    }
+
+    fn symbol(&self) -> char {
+        match self {
+            Connection::NorthSouth => '|',
+            Connection::EastWest => '-',
+            Connection::NorthEast => 'L',
+            Connection::NorthWest => 'J',
+            Connection::SouthWest => '7',
+            Connection::SouthEast => 'F',
+        }
+    }
+
+    fn box_drawing(&self) -> char {
+        match self {
+            Connection::NorthSouth => '│',
+            Connection::EastWest => '─',
+            Connection::NorthEast => '└',
+            Connection::NorthWest => '┘',
+            Connection::SouthWest => '┐',
+            Connection::SouthEast => '┌',
+        }
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -39,55 +83,75 @@ enum Cell {
 }
 
 pub struct Grid {
-    cells: Vec<Vec<Cell>>,
-    animal_position: (usize, usize),
-    polygon: Vec<(usize, usize)>,
+    cells: aoc_grid::Grid<Cell>,
+    animal_position: Position,
+    polygon: Vec<Position>,
 }
 
-impl From<&str> for Grid {
-    fn from(input: &str) -> Self {
-        let mut cells = Vec::new();
-        let mut animal_position = (0, 0);
-
-        for (i, line) in input.lines().enumerate() {
-            let mut row = Vec::new();
-            for (j, c) in line.chars().enumerate() {
-                let cell = match c {
-                    '.' => Cell::Ground,
-                    'S' => {
-                        animal_position = (i, j);
-                        Cell::Animal
-                    }
-                    '|' => Cell::Pipe(Connection::NorthSouth),
-                    '-' => Cell::Pipe(Connection::EastWest),
-                    'L' => Cell::Pipe(Connection::NorthEast),
-                    'J' => Cell::Pipe(Connection::NorthWest),
-                    '7' => Cell::Pipe(Connection::SouthWest),
-                    'F' => Cell::Pipe(Connection::SouthEast),
-                    _ => panic!("Invalid character: {}", c),
-                };
-                row.push(cell);
-            }
-            cells.push(row);
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    InvalidCharacter(char),
+    RaggedRow(RaggedRow),
+    MissingAnimal,
+    ExtraAnimal(Position),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidCharacter(c) => write!(f, "invalid character {:?}", c),
+            ErrorKind::RaggedRow(ragged_row) => write!(f, "{}", ragged_row),
+            ErrorKind::MissingAnimal => write!(f, "there is no S"),
+            ErrorKind::ExtraAnimal(first) => write!(f, "another S, the first one is at {}", describe(*first)),
         }
+    }
+}
 
-        let polygon = Vec::new();
-        Self { cells, animal_position, polygon }
+impl From<RaggedRow> for ErrorKind {
+    fn from(ragged_row: RaggedRow) -> Self {
+        ErrorKind::RaggedRow(ragged_row)
     }
 }
 
-impl Grid {
-    fn cell_connection_at(&self, position: (usize, usize), direction: (i32, i32)) -> Option<(usize, usize)> {
-        let to = (
-            (position.0 as i32 + direction.0) as usize,
-            (position.1 as i32 + direction.1) as usize,
-        );
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
 
-        if to.0 >= self.cells.len() || to.1 >= self.cells[0].len() {
-            return None;
-        }
+impl FromStr for Grid {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut animal_position = None;
 
-        let cell = &self.cells[to.0][to.1];
+        let cells = aoc_grid::Grid::parse(input, |position, c| {
+            let cell = match c {
+                '.' => Cell::Ground,
+                'S' => {
+                    if let Some(first) = animal_position {
+                        return Err(ErrorKind::ExtraAnimal(first));
+                    }
+                    animal_position = Some(position);
+                    Cell::Animal
+                }
+                '|' => Cell::Pipe(Connection::NorthSouth),
+                '-' => Cell::Pipe(Connection::EastWest),
+                'L' => Cell::Pipe(Connection::NorthEast),
+                'J' => Cell::Pipe(Connection::NorthWest),
+                '7' => Cell::Pipe(Connection::SouthWest),
+                'F' => Cell::Pipe(Connection::SouthEast),
+                _ => return Err(ErrorKind::InvalidCharacter(c)),
+            };
+            Ok(cell)
+        })?;
+
+        let animal_position = animal_position.ok_or(ParseError::new(1, 1, ErrorKind::MissingAnimal))?;
+        let polygon = Vec::new();
+        Ok(Self { cells, animal_position, polygon })
+    }
+}
+
+impl Grid {
+    fn cell_connection_at(&self, position: Position, direction: (isize, isize)) -> Option<Position> {
+        let to = self.cells.offset(position, direction)?;
+        let cell = &self.cells[to];
 
         match cell {
             Cell::Pipe(pipe) => {
@@ -134,12 +198,12 @@ impl Grid {
         }
     }
 
-    fn cell_connections(&self, position: (usize, usize)) -> [(usize, usize); 2] {
+    fn cell_connections(&self, position: Position) -> [Position; 2] {
         let mut result = [(0, 0); 2];
         let mut index = 0;
 
-        let directions = match &self.cells[position.0][position.1] {
-            Cell::Animal => [(0, 1), (1, 0), (-1, 0), (0, -1)],
+        let directions = match &self.cells[position] {
+            Cell::Animal => DIRECTIONS_4,
             Cell::Pipe(connection) => {
                 let dir = connection.directions();
                 [dir[0], dir[1], (0, 0), (0, 0)]
@@ -160,7 +224,12 @@ impl Grid {
         result
     }
 
-    pub fn distance_to_farthest_cell(&mut self) -> usize {
+    // The routes walked from both sides of the animal only meet on a closed
+    // loop, which is checked first rather than walking forever.
+    pub fn distance_to_farthest_cell(&mut self) -> Result<usize, String> {
+        self.check_animal_connections()?;
+        self.check_closed_loop()?;
+
         let mut distance = 1;
         let mut routes = self.cell_connections(self.animal_position);
         let mut previous_routes = [self.animal_position, self.animal_position];
@@ -177,8 +246,8 @@ impl Grid {
                 polygon_second_half.insert(0, routes[1]);
             }
 
-            let new_route_0 = *self.cell_connections(routes[0]).iter().filter(|&c| c != &previous_routes[0]).next().unwrap();
-            let new_route_1 = *self.cell_connections(routes[1]).iter().filter(|&c| c != &previous_routes[1]).next().unwrap();
+            let new_route_0 = *self.cell_connections(routes[0]).iter().find(|&c| c != &previous_routes[0]).unwrap();
+            let new_route_1 = *self.cell_connections(routes[1]).iter().find(|&c| c != &previous_routes[1]).unwrap();
 
             previous_routes = routes;
 
@@ -190,10 +259,15 @@ impl Grid {
 
         self.polygon.extend(polygon_second_half);
 
-        distance
+        Ok(distance)
     }
 
-    fn is_point_in_polygon(&self, p: (usize, usize)) -> bool {
+    // Cells of the loop itself, in `on_loop`, are neither inside nor outside of it.
+    fn is_point_in_polygon(&self, on_loop: &HashSet<Position>, p: Position) -> bool {
+        if on_loop.contains(&p) {
+            return false;
+        }
+
         let mut intersections = 0;
         let (xp, yp) = (p.0 as f64, p.1 as f64);
 
@@ -214,16 +288,12 @@ impl Grid {
         let mut polygon_lookup = HashSet::new();
 
         for p in self.polygon.iter() {
-            polygon_lookup.insert(p);
+            polygon_lookup.insert(*p);
         }
 
-        for i in 0..self.cells.len() {
-            for j in 0..self.cells[i].len() {
-                if self.is_point_in_polygon((i, j)) {
-                    if !polygon_lookup.contains(&(i, j)) {
-                        count += 1;
-                    }
-                }
+        for p in self.cells.positions() {
+            if self.is_point_in_polygon(&polygon_lookup, p) {
+                count += 1;
             }
         }
 
@@ -231,87 +301,256 @@ impl Grid {
     }
 }
 
+impl Solution for Grid {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+
+    // Pipes not forming a loop leave both parts unsolved, `lint` tells where.
+    fn part1(&mut self) -> Answer {
+        self.distance_to_farthest_cell().map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part2(&mut self) -> Answer {
+        // The loop has to be traced before the enclosed points can be counted.
+        match self.distance_to_farthest_cell() {
+            Ok(_) => self.count_enclosed_points().into(),
+            Err(_) => Answer::Unsolved,
+        }
+    }
+}
+
+fn describe((i, j): Position) -> String {
+    format!("line {} column {}", i + 1, j + 1)
+}
+
+impl Grid {
+    fn check_animal_connections(&self) -> Result<(), String> {
+        let connected = DIRECTIONS_4
+            .iter()
+            .filter_map(|&direction| self.cell_connection_at(self.animal_position, direction))
+            .count();
+        if connected != 2 {
+            return Err(format!("S at {} connects to {} pipes", describe(self.animal_position), connected));
+        }
+
+        Ok(())
+    }
+
+    // Follows the pipes from the animal without assuming they form a loop.
+    fn check_closed_loop(&self) -> Result<(), String> {
+        let start = self.animal_position;
+        let Some(mut current) = DIRECTIONS_4.iter().find_map(|&direction| self.cell_connection_at(start, direction)) else {
+            return Err("S connects to no pipe".into());
+        };
+        let mut previous = start;
+
+        for _ in 0..self.cells.width() * self.cells.height() {
+            if current == start {
+                return Ok(());
+            }
+            let Cell::Pipe(connection) = &self.cells[current] else {
+                return Err(format!("the pipes from S lead to another S at {}", describe(current)));
+            };
+
+            let ends = connection.directions().map(|direction| self.cell_connection_at(current, direction));
+            let next = match ends {
+                [Some(a), Some(b)] => if a == previous { b } else { a },
+                _ => return Err(format!("the pipe at {} leads nowhere", describe(current))),
+            };
+            previous = current;
+            current = next;
+        }
+
+        Err("the pipes from S never lead back to it".into())
+    }
+}
+
+impl Lint for Grid {
+    fn lint(&self) -> Vec<Check> {
+        vec![
+            Check::new("S connects to exactly two neighboring pipes", self.check_animal_connections().err()),
+            Check::new("the pipes from S form a closed loop", self.check_closed_loop().err()),
+        ]
+    }
+}
+
+const LOOP: Style = Style::color(Color::Blue).bold();
+const ANIMAL: Style = Style::color(Color::Magenta).bold();
+const INSIDE: Style = Style::color(Color::Yellow);
+const OUTSIDE: Style = Style::color(Color::Gray).dim();
+const UNTRACED: Style = Style::PLAIN.dim();
+
+impl Grid {
+    // The pipe under the animal, given by the two ends of the loop next to it.
+    fn animal_connection(&self) -> Option<Connection> {
+        let offset = |to: Position| (to.0 as isize - self.animal_position.0 as isize, to.1 as isize - self.animal_position.1 as isize);
+        Connection::from_directions([offset(self.polygon[1]), offset(*self.polygon.last()?)])
+    }
+
+    fn loop_frame(&self, traced: &HashSet<Position>, connection: &Option<Connection>) -> Frame {
+        self.cells.frame(|position, cell| match cell {
+            Cell::Animal => (connection.as_ref().map_or('S', Connection::box_drawing), ANIMAL),
+            Cell::Pipe(pipe) if traced.contains(&position) => (pipe.box_drawing(), LOOP),
+            Cell::Pipe(pipe) => (pipe.symbol(), UNTRACED),
+            Cell::Ground => ('.', UNTRACED),
+        })
+    }
+}
+
+// The loop in box drawing characters, traced both ways from the animal until
+// the farthest cell, then the cells inside of it shaded in yellow and the ones
+// outside of it in gray.
+impl Render for Grid {
+    fn render(&mut self, show: &mut dyn FnMut(&Frame)) {
+        let distance = match self.distance_to_farthest_cell() {
+            Ok(distance) => distance,
+            Err(err) => {
+                let mut frame = self.loop_frame(&HashSet::new(), &None);
+                frame.caption = format!("No loop: {}", err);
+                show(&frame);
+                return;
+            }
+        };
+        let connection = self.animal_connection();
+
+        // Around 50 frames whatever the length of the loop.
+        let step = (distance / 50).max(1);
+        let mut traced = HashSet::new();
+        for reached in (0..distance).step_by(step).chain([distance]) {
+            traced.extend(self.polygon[..=reached].iter().chain(&self.polygon[self.polygon.len() - reached..]));
+
+            let mut frame = self.loop_frame(&traced, &connection);
+            frame.caption = format!("Distance: {}", reached);
+            show(&frame);
+        }
+
+        let mut frame = self.loop_frame(&traced, &connection);
+        let mut enclosed = 0;
+        for position in self.cells.positions().filter(|position| !traced.contains(position)) {
+            if self.is_point_in_polygon(&traced, position) {
+                frame.set(position, '▒', INSIDE);
+                enclosed += 1;
+            } else {
+                frame.set(position, '░', OUTSIDE);
+            }
+        }
+        frame.caption = format!("Distance to farthest cell: {}, enclosed cells: {}", distance, enclosed);
+        show(&frame);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
-    #[test]
-    fn test_grid_from_str() {
-        let input = "\
+    const SQUARE_LOOP: &str = "\
+.....
+.S-7.
+.|.|.
+.L-J.
+.....";
+
+    const TANGLED_LOOP: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF";
+
+    const COMPLEX_LOOP: &str = "\
 ..F7.
 .FJ|.
 SJ.L7
 |F--J
 LJ...";
-        let grid = Grid::from(input);
-
-        assert_eq!(5, grid.cells.len());
-        assert_eq!(5, grid.cells[0].len());
-        assert_eq!(Cell::Ground, grid.cells[0][0]);
-        assert_eq!(Cell::Pipe(Connection::SouthEast), grid.cells[0][2]);
-        assert_eq!(Cell::Pipe(Connection::NorthWest), grid.cells[4][1]);
-        assert_eq!(Cell::Animal, grid.cells[2][0]);
+
+    const LARGER_LOOP: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...";
+
+    #[test]
+    fn test_grid_from_str() {
+        let grid = COMPLEX_LOOP.parse::<Grid>().unwrap();
+
+        assert_eq!(5, grid.cells.height());
+        assert_eq!(5, grid.cells.width());
+        assert_eq!(Cell::Ground, grid.cells[(0, 0)]);
+        assert_eq!(Cell::Pipe(Connection::SouthEast), grid.cells[(0, 2)]);
+        assert_eq!(Cell::Pipe(Connection::NorthWest), grid.cells[(4, 1)]);
+        assert_eq!(Cell::Animal, grid.cells[(2, 0)]);
         assert_eq!((2, 0), grid.animal_position);
     }
 
+    #[test]
+    fn test_grid_from_invalid_str() {
+        let err = COMPLEX_LOOP.replace("|F--J", "|F-+J").parse::<Grid>().err();
+
+        assert_eq!(Some(ParseError::new(4, 4, ErrorKind::InvalidCharacter('+'))), err);
+
+        let err = ".F7\n.LJ\n...".parse::<Grid>().err();
+        assert_eq!(Some(ParseError::new(1, 1, ErrorKind::MissingAnimal)), err);
+
+        let err = ".....\n.S-7.\n.|.|.\n.L-S.\n.....".parse::<Grid>().err();
+        assert_eq!(Some(ParseError::new(4, 4, ErrorKind::ExtraAnimal((1, 1)))), err);
+        assert_eq!("line 4, column 4: another S, the first one is at line 2 column 2", err.unwrap().to_string());
+    }
+
     #[test]
     fn test_grid_cell_connections() {
-        let input = "\
-..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...";
-        let grid = Grid::from(input);
+        let grid = COMPLEX_LOOP.parse::<Grid>().unwrap();
 
         assert_eq!([(2, 1), (3, 0)], grid.cell_connections((2, 0)));
         assert_eq!([(1, 1), (2, 0)], grid.cell_connections((2, 1)));
     }
 
+    #[test]
+    fn test_grid_without_loop() {
+        let mut grid = "S-7\n|.|\nL-|".parse::<Grid>().unwrap();
+
+        assert_eq!(Err("the pipe at line 3 column 3 leads nowhere".to_string()), grid.distance_to_farthest_cell());
+        assert_eq!(Answer::Unsolved, grid.part1());
+        assert_eq!(Answer::Unsolved, grid.part2());
+
+        let mut grid = ".....\n-S-7.\n.|.|.\n.L-J.\n.....".parse::<Grid>().unwrap();
+        assert_eq!(Err("S at line 2 column 2 connects to 3 pipes".to_string()), grid.distance_to_farthest_cell());
+    }
+
     #[test]
     fn test_grid_distance_to_farthest_cell() {
-        let input = "\
-..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...";
-        let mut grid = Grid::from(input);
-        assert_eq!(8, grid.distance_to_farthest_cell());
+        let mut grid = COMPLEX_LOOP.parse::<Grid>().unwrap();
+        assert_eq!(Ok(8), grid.distance_to_farthest_cell());
 
-        let input = "\
-.....
-.S-7.
-.|.|.
-.L-J.
-.....";
-        let mut grid = Grid::from(input);
-        assert_eq!(4, grid.distance_to_farthest_cell());
+        let mut grid = SQUARE_LOOP.parse::<Grid>().unwrap();
+        assert_eq!(Ok(4), grid.distance_to_farthest_cell());
 
-        let input = "\
--L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF";
-        let mut grid = Grid::from(input);
-        assert_eq!(4, grid.distance_to_farthest_cell());
+        let mut grid = TANGLED_LOOP.parse::<Grid>().unwrap();
+        assert_eq!(Ok(4), grid.distance_to_farthest_cell());
         assert_eq!(
             vec![(1, 1), (1, 2), (1, 3), (2, 3), (3, 3), (3, 2), (3, 1), (2, 1)],
             grid.polygon,
         );
     }
 
+    fn on_loop(grid: &Grid) -> HashSet<Position> {
+        grid.polygon.iter().copied().collect()
+    }
+
     #[test]
     fn test_grid_is_point_in_polygon() {
-        let input = "\
-..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...";
-        let mut grid = Grid::from(input);
-        assert_eq!(8, grid.distance_to_farthest_cell());
+        let mut grid = COMPLEX_LOOP.parse::<Grid>().unwrap();
+        assert_eq!(Ok(8), grid.distance_to_farthest_cell());
         assert_eq!(
             vec![
                 (2, 0), (2, 1), (1, 1), (1, 2), (0, 2), (0, 3),
@@ -319,38 +558,229 @@ LJ...";
             ],
             grid.polygon,
         );
-        assert_eq!(false, grid.is_point_in_polygon((2, 0)));
-        assert_eq!(false, grid.is_point_in_polygon((1, 0)));
-        assert_eq!(true, grid.is_point_in_polygon((2, 2)));
+        assert!(!grid.is_point_in_polygon(&on_loop(&grid), (2, 0)));
+        assert!(!grid.is_point_in_polygon(&on_loop(&grid), (1, 0)));
+        assert!(grid.is_point_in_polygon(&on_loop(&grid), (2, 2)));
 
-        let input = "\
--L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF";
-        let mut grid = Grid::from(input);
-        assert_eq!(4, grid.distance_to_farthest_cell());
+        let mut grid = TANGLED_LOOP.parse::<Grid>().unwrap();
+        assert_eq!(Ok(4), grid.distance_to_farthest_cell());
         assert_eq!(
             vec![(1, 1), (1, 2), (1, 3), (2, 3), (3, 3), (3, 2), (3, 1), (2, 1)],
             grid.polygon,
         );
-        assert_eq!(true, grid.is_point_in_polygon((2, 2)));
+        assert!(grid.is_point_in_polygon(&on_loop(&grid), (2, 2)));
 
-        let input = "\
-.F----7F7F7F7F-7....
-.|F--7||||||||FJ....
-.||.FJ||||||||L7....
-FJL7L7LJLJ||LJ.L-7..
-L--J.L7...LJS7F-7L7.
-....F-J..F7FJ|L7L7L7
-....L7.F7||L7|.L7L7|
-.....|FJLJ|FJ|F7|.LJ
-....FJL-7.||.||||...
-....L---J.LJ.LJLJ...";
-        let mut grid = Grid::from(input);
-        assert_eq!(70, grid.distance_to_farthest_cell());
-        assert_eq!(true, grid.is_point_in_polygon((4, 7)));
-        assert_eq!(false, grid.is_point_in_polygon((0, 0)));
+        let mut grid = LARGER_LOOP.parse::<Grid>().unwrap();
+        assert_eq!(Ok(70), grid.distance_to_farthest_cell());
+        assert!(grid.is_point_in_polygon(&on_loop(&grid), (4, 7)));
+        assert!(!grid.is_point_in_polygon(&on_loop(&grid), (0, 0)));
+    }
+
+    // A random loop following the boundary of a polyomino made of columns of
+    // cells, each column overlapping the previous one so that the polyomino
+    // has neither holes nor cells touching only by a corner. The polyomino is
+    // drawn at twice its size, which leaves a single line of pipes around it.
+    // Returns the rows of pipes, without the animal, and the loop in order.
+    fn polyomino_loop(columns: &[(usize, usize)]) -> (Vec<Vec<char>>, Vec<Position>) {
+        let mut intervals: Vec<(usize, usize)> = Vec::new();
+        for &(a, b) in columns {
+            let (mut top, mut bottom) = (a.min(b), a.max(b));
+            if let Some(&(previous_top, previous_bottom)) = intervals.last() {
+                bottom = bottom.max(previous_top);
+                top = top.min(previous_bottom);
+            }
+            intervals.push((top, bottom));
+        }
+
+        let height = 2 * intervals.iter().map(|&(_, bottom)| bottom + 1).max().unwrap() + 3;
+        let width = 2 * intervals.len() + 3;
+
+        // Coarse cell (i, j) covers the 3x3 block of fine cells around (2i + 2, 2j + 2).
+        let mut covered = vec![vec![false; width]; height];
+        for (j, &(top, bottom)) in intervals.iter().enumerate() {
+            for i in top..=bottom {
+                for (di, dj) in (0..3).flat_map(|di| (0..3).map(move |dj| (di, dj))) {
+                    covered[2 * i + 1 + di][2 * j + 1 + dj] = true;
+                }
+            }
+        }
+
+        let is_loop = |(i, j): Position| {
+            covered[i][j] && (i - 1..=i + 1).any(|k| (j - 1..=j + 1).any(|l| !covered[k][l]))
+        };
+        let loop_neighbors = |(i, j): Position| {
+            [(i - 1, j), (i + 1, j), (i, j - 1), (i, j + 1)]
+                .into_iter()
+                .filter(|&p| is_loop(p))
+                .collect::<Vec<_>>()
+        };
+
+        let start = (1..height).flat_map(|i| (1..width).map(move |j| (i, j))).find(|&p| is_loop(p)).unwrap();
+        let mut polygon = vec![start];
+        let mut current = loop_neighbors(start)[0];
+        while current != start {
+            let previous = polygon[polygon.len() - 1];
+            polygon.push(current);
+            current = *loop_neighbors(current).iter().find(|&&p| p != previous).unwrap();
+        }
+
+        let mut rows = vec![vec!['.'; width]; height];
+        for (k, &(i, j)) in polygon.iter().enumerate() {
+            let before = polygon[(k + polygon.len() - 1) % polygon.len()];
+            let after = polygon[(k + 1) % polygon.len()];
+            let north = before == (i - 1, j) || after == (i - 1, j);
+            let south = before == (i + 1, j) || after == (i + 1, j);
+            let west = before == (i, j - 1) || after == (i, j - 1);
+
+            rows[i][j] = match (north, south, west) {
+                (true, true, _) => '|',
+                (true, false, true) => 'J',
+                (true, false, false) => 'L',
+                (false, true, true) => '7',
+                (false, true, false) => 'F',
+                (false, false, _) => '-',
+            };
+        }
+
+        (rows, polygon)
+    }
+
+    // Reference implementation drawing every cell of `rows` at three times its
+    // size and flooding the outside, the enclosed cells are the loop-free ones
+    // the flood does not reach.
+    fn flood_fill_enclosed_points(rows: &[Vec<char>], polygon: &[Position]) -> usize {
+        let (height, width) = (rows.len(), rows[0].len());
+        let on_loop = polygon.iter().collect::<HashSet<_>>();
+
+        let mut blocked = vec![vec![false; 3 * width]; 3 * height];
+        for &&(i, j) in &on_loop {
+            let (ci, cj) = (3 * i + 1, 3 * j + 1);
+            blocked[ci][cj] = true;
+            let arms: &[(isize, isize)] = match rows[i][j] {
+                '|' => &[(-1, 0), (1, 0)],
+                '-' => &[(0, -1), (0, 1)],
+                'L' => &[(-1, 0), (0, 1)],
+                'J' => &[(-1, 0), (0, -1)],
+                '7' => &[(1, 0), (0, -1)],
+                _ => &[(1, 0), (0, 1)],
+            };
+            for &(di, dj) in arms {
+                blocked[(ci as isize + di) as usize][(cj as isize + dj) as usize] = true;
+            }
+        }
+
+        let mut outside = vec![vec![false; 3 * width]; 3 * height];
+        let mut stack = vec![(0, 0)];
+        while let Some((i, j)) = stack.pop() {
+            if outside[i][j] || blocked[i][j] {
+                continue;
+            }
+            outside[i][j] = true;
+            if i > 0 {
+                stack.push((i - 1, j));
+            }
+            if j > 0 {
+                stack.push((i, j - 1));
+            }
+            if i + 1 < 3 * height {
+                stack.push((i + 1, j));
+            }
+            if j + 1 < 3 * width {
+                stack.push((i, j + 1));
+            }
+        }
+
+        (0..height)
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .filter(|p| !on_loop.contains(p) && !outside[3 * p.0 + 1][3 * p.1 + 1])
+            .count()
+    }
+
+    fn violations(input: &str) -> Vec<Option<String>> {
+        Grid::parse(input).unwrap().lint().into_iter().map(|check| check.violation).collect()
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(vec![None, None], violations(SQUARE_LOOP));
+        assert_eq!(vec![None, None], violations(TANGLED_LOOP));
+        assert_eq!(vec![None, None], violations(LARGER_LOOP));
+
+        assert_eq!(
+            vec![Some("S at line 2 column 2 connects to 3 pipes".to_string()), None],
+            violations(".....\n-S-7.\n.|.|.\n.L-J.\n....."),
+        );
+        assert_eq!(
+            vec![None, Some("the pipe at line 3 column 4 leads nowhere".to_string())],
+            violations(".....\n.S-7.\n.|.|.\n.L-..\n....."),
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let mut frames = Vec::new();
+        Grid::parse(SQUARE_LOOP).unwrap().render(&mut |frame| frames.push(frame.clone()));
+
+        assert_eq!(
+            vec![
+                ".....\n.┌-7.\n.|.|.\n.L-J.\n.....\nDistance: 0\n",
+                ".....\n.┌─7.\n.│.|.\n.L-J.\n.....\nDistance: 1\n",
+                ".....\n.┌─┐.\n.│.|.\n.└-J.\n.....\nDistance: 2\n",
+                ".....\n.┌─┐.\n.│.│.\n.└─J.\n.....\nDistance: 3\n",
+                ".....\n.┌─┐.\n.│.│.\n.└─┘.\n.....\nDistance: 4\n",
+                "░░░░░\n░┌─┐░\n░│▒│░\n░└─┘░\n░░░░░\nDistance to farthest cell: 4, enclosed cells: 1\n",
+            ],
+            frames.iter().map(Frame::to_plain).collect::<Vec<_>>(),
+        );
+
+        let last = frames.last().unwrap();
+        assert_eq!(('┌', ANIMAL), last.get((1, 1)));
+        assert_eq!(('─', LOOP), last.get((1, 2)));
+        assert_eq!(('▒', INSIDE), last.get((2, 2)));
+        assert_eq!(('░', OUTSIDE), last.get((0, 0)));
+        assert_eq!(('7', UNTRACED), frames[0].get((1, 3)));
+    }
+
+    #[test]
+    fn test_render_larger_loop() {
+        let mut grid = Grid::parse(LARGER_LOOP).unwrap();
+        let mut last = None;
+        grid.render(&mut |frame| last = Some(frame.to_plain()));
+
+        let last = last.unwrap();
+        assert_eq!(8, last.matches('▒').count());
+        assert!(last.ends_with("Distance to farthest cell: 70, enclosed cells: 8\n"));
+    }
+
+    proptest! {
+        #[test]
+        fn test_enclosed_points_match_flood_fill(
+            columns in prop::collection::vec((0usize..5, 0usize..5), 1..7),
+            animal in any::<prop::sample::Index>(),
+            junk in prop::collection::vec(prop::sample::select(vec!['.', '.', '.', '|', '-', 'L', 'J', '7', 'F']), 256),
+        ) {
+            let (mut rows, polygon) = polyomino_loop(&columns);
+            let expected = flood_fill_enclosed_points(&rows, &polygon);
+
+            // Pipes off the loop count as enclosed as well, as long as they do
+            // not make it ambiguous how the animal's cell connects.
+            let (ai, aj) = polygon[animal.index(polygon.len())];
+            let animal_neighbors = [(ai - 1, aj), (ai + 1, aj), (ai, aj - 1), (ai, aj + 1)];
+            let width = rows[0].len();
+            for (i, row) in rows.iter_mut().enumerate() {
+                for (j, c) in row.iter_mut().enumerate() {
+                    if *c == '.' && !animal_neighbors.contains(&(i, j)) {
+                        *c = junk[(i * width + j) % junk.len()];
+                    }
+                }
+            }
+            rows[ai][aj] = 'S';
+
+            let input = rows.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>().join("\n");
+            let mut grid = input.parse::<Grid>().unwrap();
+
+            prop_assert_eq!(Ok(polygon.len() / 2), grid.distance_to_farthest_cell());
+            prop_assert_eq!(expected, grid.count_enclosed_points());
+        }
     }
 }
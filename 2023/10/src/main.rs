@@ -1,9 +1,11 @@
+use aoc_core::{input, parse};
 use day_10::grid;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let mut grid = grid::Grid::from(input);
-    println!("Distance to farthest cell: {}", grid.distance_to_farthest_cell());
+    let input = input::from_args_or_exit(10);
+    let mut grid = parse::or_exit(10, input.parse::<grid::Grid>());
+    let distance = parse::or_exit(10, grid.distance_to_farthest_cell());
+    println!("Distance to farthest cell: {}", distance);
 
     println!("Number of enclosed cells: {}", grid.count_enclosed_points());
 }
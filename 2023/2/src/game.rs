@@ -0,0 +1,300 @@
+/*
+Example input, games:
+
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+
+Cubes in the bag: 12 red cubes, 13 green cubes, and 14 blue cubes
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::{parse, Answer, Solution};
+
+#[derive(Eq, PartialEq, Hash, Clone)]
+pub enum Cube {
+    Red,
+    Green,
+    Blue,
+}
+
+pub struct Round {
+    cubes: HashMap<Cube, u32>,
+}
+
+pub struct Game {
+    total_cubes: HashMap<Cube, u32>,
+    id: u32,
+    rounds: Vec<Round>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    MissingRounds,
+    MissingId,
+    MissingCount,
+    MissingColor,
+    InvalidNumber(String),
+    InvalidColor(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingRounds => write!(f, "expected \": \" followed by the rounds"),
+            ErrorKind::MissingId => write!(f, "missing game id"),
+            ErrorKind::MissingCount => write!(f, "missing cube count"),
+            ErrorKind::MissingColor => write!(f, "missing cube color"),
+            ErrorKind::InvalidNumber(s) => write!(f, "invalid number {:?}", s),
+            ErrorKind::InvalidColor(s) => write!(f, "invalid color {:?}", s),
+        }
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+fn error(line: &str, token: &str, kind: ErrorKind) -> ParseError {
+    ParseError::new(1, parse::column(line, token), kind)
+}
+
+fn parse_number(line: &str, token: &str) -> Result<u32, ParseError> {
+    token
+        .parse::<u32>()
+        .map_err(|_| error(line, token, ErrorKind::InvalidNumber(token.to_string())))
+}
+
+impl Round {
+    // `s` is a slice of `line`, errors are reported at their column in `line`.
+    fn parse(line: &str, s: &str) -> Result<Self, ParseError> {
+        let mut cubes = HashMap::new();
+        for c in s.split(", ") {
+            let mut iter = c.split_whitespace();
+            let count_str = iter.next().ok_or_else(|| error(line, c, ErrorKind::MissingCount))?;
+            let count = parse_number(line, count_str)?;
+            let color_str = iter.next().ok_or_else(|| error(line, &c[c.len()..], ErrorKind::MissingColor))?;
+            let cube = match color_str {
+                "red" => Cube::Red,
+                "green" => Cube::Green,
+                "blue" => Cube::Blue,
+                _ => return Err(error(line, color_str, ErrorKind::InvalidColor(color_str.to_string()))),
+            };
+            cubes.insert(cube, count);
+        }
+        Ok(Self { cubes })
+    }
+}
+
+impl FromStr for Round {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Round::parse(s, s)
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name_str, rounds_str) = s
+            .split_once(": ")
+            .ok_or_else(|| error(s, &s[s.len()..], ErrorKind::MissingRounds))?;
+
+        let id_str = name_str
+            .split_whitespace()
+            .nth(1)
+            .ok_or_else(|| error(s, &name_str[name_str.len()..], ErrorKind::MissingId))?;
+        let id = parse_number(s, id_str)?;
+
+        let mut rounds = Vec::new();
+        for r in rounds_str.split("; ") {
+            rounds.push(Round::parse(s, r)?);
+        }
+
+        let total_cubes = [
+            (Cube::Red, 12),
+            (Cube::Green, 13),
+            (Cube::Blue, 14),
+        ].iter().cloned().collect();
+
+        Ok(Self { total_cubes, id, rounds })
+    }
+}
+
+impl Game {
+    pub fn is_valid(&self) -> bool {
+        for r in &self.rounds {
+            for (cube, count) in &r.cubes {
+                let max_available_count = self.total_cubes.get(cube).unwrap();
+                if count > max_available_count {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+pub fn find_fewest_number_of_each_cube_possible(g: &Game) -> HashMap<&Cube, u32> {
+    let mut cubes_with_max_count = HashMap::new();
+
+    for r in &g.rounds {
+        for (cube, count) in &r.cubes {
+            let max_count = cubes_with_max_count.entry(cube).or_insert(*count);
+            if count > max_count {
+                *max_count = *count;
+            }
+        }
+    }
+
+    cubes_with_max_count
+}
+
+pub fn power_of_cubes(cubes: &HashMap<&Cube, u32>) -> u32 {
+    let mut power = 1;
+
+    for count in cubes.values() {
+        power *= count
+    }
+
+    power
+}
+
+pub fn sum_of_valid_game_ids(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .filter(|g| g.is_valid())
+        .map(|g| g.id)
+        .sum::<u32>()
+}
+
+pub fn sum_of_powers(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(|g| {
+            let cubes_with_max_count = find_fewest_number_of_each_cube_possible(g);
+            power_of_cubes(&cubes_with_max_count)
+        })
+        .sum::<u32>()
+}
+
+pub struct Games {
+    games: Vec<Game>,
+}
+
+impl Solution for Games {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Ok(Self { games: parse::lines(input, str::parse)? })
+    }
+
+    fn part1(&mut self) -> Answer {
+        sum_of_valid_game_ids(&self.games).into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        sum_of_powers(&self.games).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_from() {
+        let r = "3 blue, 4 red".parse::<Round>().unwrap();
+
+        assert_eq!(r.cubes.get(&Cube::Blue).unwrap(), &3);
+        assert_eq!(r.cubes.get(&Cube::Red).unwrap(), &4);
+    }
+
+    #[test]
+    fn test_round_from_all_colors() {
+        let r = "3 blue, 4 red, 5 green".parse::<Round>().unwrap();
+
+        assert_eq!(r.cubes.get(&Cube::Blue).unwrap(), &3);
+        assert_eq!(r.cubes.get(&Cube::Red).unwrap(), &4);
+        assert_eq!(r.cubes.get(&Cube::Green).unwrap(), &5);
+    }
+
+    #[test]
+    fn test_round_from_invalid_str() {
+        let err = "3 blue, 4 red, 5 green, 6 yellow".parse::<Round>().err();
+
+        assert_eq!(Some(ParseError::new(1, 27, ErrorKind::InvalidColor("yellow".into()))), err);
+    }
+
+    #[test]
+    fn test_game_from_invalid_str() {
+        let err = "Game 5: 3 blue, 4 red; 1 red, 2 green, x blue".parse::<Game>().err();
+        assert_eq!(Some(ParseError::new(1, 40, ErrorKind::InvalidNumber("x".into()))), err);
+
+        let err = "Game 5 3 blue".parse::<Game>().err();
+        assert_eq!(Some(ParseError::new(1, 14, ErrorKind::MissingRounds)), err);
+
+        let err = "Game 5: 3 blue, 4".parse::<Game>().err();
+        assert_eq!(Some(ParseError::new(1, 18, ErrorKind::MissingColor)), err);
+    }
+
+    #[test]
+    fn test_games_parse_reports_line() {
+        let err = Games::parse("Game 1: 3 blue\nGame 2: 4 purple").err();
+
+        assert_eq!(Some(ParseError::new(2, 11, ErrorKind::InvalidColor("purple".into()))), err);
+    }
+
+    #[test]
+    fn test_game_from() {
+        let g = "Game 5: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".parse::<Game>().unwrap();
+
+        assert_eq!(g.id, 5);
+        assert_eq!(g.rounds.len(), 3);
+        assert_eq!(g.rounds[0].cubes.get(&Cube::Blue).unwrap(), &3);
+        assert_eq!(g.rounds[0].cubes.get(&Cube::Red).unwrap(), &4);
+        assert_eq!(g.rounds[1].cubes.get(&Cube::Red).unwrap(), &1);
+        assert_eq!(g.rounds[1].cubes.get(&Cube::Green).unwrap(), &2);
+        assert_eq!(g.rounds[1].cubes.get(&Cube::Blue).unwrap(), &6);
+        assert_eq!(g.rounds[2].cubes.get(&Cube::Green).unwrap(), &2);
+    }
+
+    #[test]
+    fn test_game_is_valid() {
+        let g = "Game 5: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".parse::<Game>().unwrap();
+
+        assert!(g.is_valid());
+    }
+
+    #[test]
+    fn test_game_is_not_valid() {
+        let g = "Game 5: 3 blue, 13 red; 1 red, 2 green, 6 blue; 2 green".parse::<Game>().unwrap();
+
+        assert!(!g.is_valid());
+    }
+
+    #[test]
+    fn test_find_fewest_number_of_each_cube_possible() {
+        let g = "Game 5: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green".parse::<Game>().unwrap();
+
+        let cubes_with_max_count = find_fewest_number_of_each_cube_possible(&g);
+
+        assert_eq!(cubes_with_max_count.get(&Cube::Blue).unwrap(), &6);
+        assert_eq!(cubes_with_max_count.get(&Cube::Red).unwrap(), &4);
+        assert_eq!(cubes_with_max_count.get(&Cube::Green).unwrap(), &2);
+    }
+
+    #[test]
+    fn test_power_of_cubes() {
+        let mut cubes = HashMap::new();
+        cubes.insert(&Cube::Blue, 6);
+        cubes.insert(&Cube::Red, 4);
+        cubes.insert(&Cube::Green, 2);
+
+        assert_eq!(power_of_cubes(&cubes), 48);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Range;
+use aoc_core::render::{Color, Frame, Render, Style};
+use aoc_core::{Answer, Solution};
+use aoc_grid::{Grid, Position, RaggedRow};
+
+fn is_symbol(el: u8) -> bool {
+    el != b'.' && !el.is_ascii_digit()
+}
+
+fn has_adjacent_symbol(engine_schematic: &Grid<u8>, position: Position) -> bool {
+    engine_schematic
+        .neighbors8(position)
+        .any(|neighbor| is_symbol(engine_schematic[neighbor]))
+}
+
+pub fn sum_of_part_numbers(engine_schematic: &Grid<u8>) -> u32 {
+    numbers(engine_schematic)
+        .iter()
+        .filter(|number| number.is_part)
+        .map(|number| number.value)
+        .sum()
+}
+
+pub fn sum_of_gear_ratios(engine_schematic: &Grid<u8>) -> u32 {
+    numbers_by_gear(&numbers(engine_schematic))
+        .values()
+        .filter(|numbers| numbers.len() == 2)
+        .map(|numbers| numbers.iter().map(|number| number.value).product::<u32>())
+        .sum()
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    NonAsciiCharacter(char),
+    RaggedRow(RaggedRow),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::NonAsciiCharacter(c) => write!(f, "non ASCII character {:?}", c),
+            ErrorKind::RaggedRow(ragged_row) => write!(f, "{}", ragged_row),
+        }
+    }
+}
+
+impl From<RaggedRow> for ErrorKind {
+    fn from(ragged_row: RaggedRow) -> Self {
+        ErrorKind::RaggedRow(ragged_row)
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(input, |_, c| {
+        if c.is_ascii() {
+            Ok(c as u8)
+        } else {
+            Err(ErrorKind::NonAsciiCharacter(c))
+        }
+    })
+}
+
+pub struct EngineSchematic {
+    grid: Grid<u8>,
+}
+
+impl Solution for EngineSchematic {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Ok(Self { grid: parse(input)? })
+    }
+
+    fn part1(&mut self) -> Answer {
+        sum_of_part_numbers(&self.grid).into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        sum_of_gear_ratios(&self.grid).into()
+    }
+}
+
+// A number of the schematic with the gear symbols next to it, scanned once
+// for both parts and for drawing.
+struct Number {
+    row: usize,
+    columns: Range<usize>,
+    value: u32,
+    is_part: bool,
+    gears: HashSet<Position>,
+}
+
+fn numbers(engine_schematic: &Grid<u8>) -> Vec<Number> {
+    let mut numbers = Vec::new();
+
+    for (i, row) in engine_schematic.rows().enumerate() {
+        let mut j = 0;
+        while j < row.len() {
+            if !row[j].is_ascii_digit() {
+                j += 1;
+                continue;
+            }
+
+            let start = j;
+            while j < row.len() && row[j].is_ascii_digit() {
+                j += 1;
+            }
+            let gears = (start..j)
+                .flat_map(|column| engine_schematic.neighbors8((i, column)))
+                .filter(|&neighbor| engine_schematic[neighbor] == b'*')
+                .collect();
+
+            numbers.push(Number {
+                row: i,
+                columns: start..j,
+                value: row[start..j].iter().fold(0, |value, &digit| value * 10 + (digit - b'0') as u32),
+                is_part: (start..j).any(|column| has_adjacent_symbol(engine_schematic, (i, column))),
+                gears,
+            });
+        }
+    }
+
+    numbers
+}
+
+fn numbers_by_gear(numbers: &[Number]) -> HashMap<Position, Vec<&Number>> {
+    let mut numbers_by_gear: HashMap<Position, Vec<&Number>> = HashMap::new();
+    for number in numbers {
+        for &gear in &number.gears {
+            numbers_by_gear.entry(gear).or_default().push(number);
+        }
+    }
+
+    numbers_by_gear
+}
+
+const PART_NUMBER: Style = Style::color(Color::Green).bold();
+const OTHER_NUMBER: Style = Style::color(Color::Red);
+const GEAR: Style = Style::color(Color::Yellow).bold();
+const SYMBOL: Style = Style::color(Color::Cyan);
+const BLANK: Style = Style::color(Color::Gray).dim();
+const UNSCANNED: Style = Style::PLAIN.dim();
+
+// The schematic with part numbers in green, other numbers in red and gears in
+// yellow, revealed one row at a time. A gear is only known once the row
+// below it has been scanned as well.
+impl Render for EngineSchematic {
+    fn render(&mut self, show: &mut dyn FnMut(&Frame)) {
+        let numbers = numbers(&self.grid);
+        let numbers_by_gear = numbers_by_gear(&numbers);
+
+        for scanned in 1..=self.grid.height() {
+            let mut frame = self.grid.frame(|(i, _), &el| {
+                let style = match el {
+                    _ if i >= scanned => UNSCANNED,
+                    b'.' => BLANK,
+                    _ => SYMBOL,
+                };
+                (el as char, style)
+            });
+
+            let mut part_numbers = 0;
+            for number in numbers.iter().filter(|number| number.row < scanned) {
+                let style = if number.is_part { PART_NUMBER } else { OTHER_NUMBER };
+                for column in number.columns.clone() {
+                    frame.set((number.row, column), self.grid[(number.row, column)] as char, style);
+                }
+                if number.is_part {
+                    part_numbers += number.value;
+                }
+            }
+
+            let mut gear_ratios = 0;
+            for (&gear, numbers) in &numbers_by_gear {
+                if numbers.len() == 2 && (gear.0 + 1 < scanned || scanned == self.grid.height()) {
+                    frame.set(gear, '*', GEAR);
+                    gear_ratios += numbers[0].value * numbers[1].value;
+                }
+            }
+
+            frame.caption = format!("Sum of part numbers: {}, sum of gear ratios: {}", part_numbers, gear_ratios);
+            show(&frame);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_adjacent_symbol() {
+        let text = "\
+467..114..
+?.........
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+        let engine_schematic = parse(text).unwrap();
+
+        assert!(has_adjacent_symbol(&engine_schematic, (0, 0)));
+        assert!(has_adjacent_symbol(&engine_schematic, (0, 1)));
+        assert!(!has_adjacent_symbol(&engine_schematic, (0, 2)));
+
+        assert!(!has_adjacent_symbol(&engine_schematic, (0, 5)));
+        assert!(!has_adjacent_symbol(&engine_schematic, (0, 6)));
+        assert!(!has_adjacent_symbol(&engine_schematic, (0, 7)));
+    }
+
+    #[test]
+    fn test_render() {
+        let text = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+        let mut frames = Vec::new();
+        EngineSchematic::parse(text).unwrap().render(&mut |frame| frames.push(frame.clone()));
+        assert_eq!(10, frames.len());
+
+        let first = &frames[0];
+        assert_eq!(('4', PART_NUMBER), first.get((0, 0)));
+        assert_eq!(('1', OTHER_NUMBER), first.get((0, 5)));
+        assert_eq!(('*', UNSCANNED), first.get((1, 3)));
+        assert_eq!("Sum of part numbers: 467, sum of gear ratios: 0", first.caption);
+
+        // The gear between 467 and 35 only shows once the row below it is scanned.
+        assert_eq!(('*', SYMBOL), frames[1].get((1, 3)));
+        assert_eq!(('*', GEAR), frames[2].get((1, 3)));
+
+        let last = frames.last().unwrap();
+        assert_eq!(format!("{}\nSum of part numbers: 4361, sum of gear ratios: 467835\n", text), last.to_plain());
+        assert_eq!(('*', GEAR), last.get((8, 5)));
+        assert_eq!(('*', SYMBOL), last.get((4, 3)));
+        assert_eq!(('5', OTHER_NUMBER), last.get((5, 7)));
+        assert_eq!(('.', BLANK), last.get((0, 3)));
+    }
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        let err = parse("467..114..\n...*.....\n..35..633.").err();
+
+        assert_eq!(Some(ParseError::new(2, 10, ErrorKind::RaggedRow(RaggedRow { expected: 10, found: 9 }))), err);
+    }
+}
@@ -1,4 +1,8 @@
 use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::lint::{Check, Lint};
+use aoc_core::{parse, Answer, Solution};
 
 #[derive(Debug, Clone)]
 pub struct Card {
@@ -7,19 +11,54 @@ pub struct Card {
     numbers: Vec<u32>,
 }
 
-impl From<&str> for Card {
-    fn from(s: &str) -> Self {
-        let (name_str, all_numbers_str) = s.split_once(": ").unwrap();
-        let id = name_str.split_whitespace().nth(1).unwrap().parse::<u32>().unwrap();
-        let (winning_numbers_str, numbers_str) = all_numbers_str.split_once(" | ").unwrap();
-        let winning_numbers = winning_numbers_str.split_whitespace().map(|s| s.parse::<u32>().unwrap()).collect();
-        let numbers = numbers_str.split_whitespace().map(|s| s.parse::<u32>().unwrap()).collect();
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    MissingNumbers,
+    MissingId,
+    MissingSeparator,
+    InvalidNumber(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingNumbers => write!(f, "expected \": \" followed by the numbers"),
+            ErrorKind::MissingId => write!(f, "missing card id"),
+            ErrorKind::MissingSeparator => write!(f, "expected \" | \" between the winning numbers and the numbers"),
+            ErrorKind::InvalidNumber(s) => write!(f, "invalid number {:?}", s),
+        }
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+fn error(line: &str, token: &str, kind: ErrorKind) -> ParseError {
+    ParseError::new(1, parse::column(line, token), kind)
+}
+
+fn parse_number(line: &str, token: &str) -> Result<u32, ParseError> {
+    token
+        .parse::<u32>()
+        .map_err(|_| error(line, token, ErrorKind::InvalidNumber(token.to_string())))
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
 
-        Card {
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let end = &s[s.len()..];
+        let (name_str, all_numbers_str) = s.split_once(": ").ok_or_else(|| error(s, end, ErrorKind::MissingNumbers))?;
+        let id_str = name_str.split_whitespace().nth(1).ok_or_else(|| error(s, &name_str[name_str.len()..], ErrorKind::MissingId))?;
+        let id = parse_number(s, id_str)?;
+        let (winning_numbers_str, numbers_str) = all_numbers_str.split_once(" | ").ok_or_else(|| error(s, end, ErrorKind::MissingSeparator))?;
+        let winning_numbers = winning_numbers_str.split_whitespace().map(|n| parse_number(s, n)).collect::<Result<_, _>>()?;
+        let numbers = numbers_str.split_whitespace().map(|n| parse_number(s, n)).collect::<Result<_, _>>()?;
+
+        Ok(Card {
             id,
             winning_numbers,
             numbers,
-        }
+        })
     }
 }
 
@@ -45,31 +84,145 @@ impl Card {
     }
 }
 
+pub fn expand_cards(cards: &[Card], original_cards: &[Card]) -> Vec<Card> {
+    let mut expanded_cards = cards.to_vec();
+    let mut new_cards = Vec::new();
+
+    for card in cards {
+        let number_of_matches = card.get_number_of_matches();
+        let id = card.get_id() as usize;
+
+        if number_of_matches > 0 {
+            for card in &original_cards[id..(id + number_of_matches)] {
+                new_cards.push(card.clone());
+            }
+        }
+    }
+
+    if new_cards.is_empty() {
+        return expanded_cards;
+    }
+
+    for card in expand_cards(&new_cards, original_cards) {
+        expanded_cards.push(card);
+    }
+
+    expanded_cards
+}
+
+pub struct Scratchcards {
+    cards: Vec<Card>,
+}
+
+impl Solution for Scratchcards {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Ok(Self { cards: parse::lines(input, str::parse)? })
+    }
+
+    fn part1(&mut self) -> Answer {
+        self.cards
+            .iter()
+            .map(|c| c.point_value().unwrap_or(0))
+            .sum::<u32>()
+            .into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        expand_cards(&self.cards, &self.cards).len().into()
+    }
+}
+
+impl Lint for Scratchcards {
+    fn lint(&self) -> Vec<Check> {
+        // `expand_cards` looks cards up by id, as indices into the table.
+        let misnumbered = self.cards
+            .iter()
+            .enumerate()
+            .find(|(i, card)| card.id as usize != i + 1)
+            .map(|(i, card)| format!("card on line {} has id {}", i + 1, card.id));
+
+        let overflowing = self.cards
+            .iter()
+            .find(|card| card.get_number_of_matches() > 0 && card.id as usize + card.get_number_of_matches() > self.cards.len())
+            .map(|card| format!("card {} wins copies up to card {}, the last card is {}", card.id, card.id as usize + card.get_number_of_matches(), self.cards.len()));
+
+        vec![
+            Check::new("card ids are 1, 2, 3, ... in order", misnumbered),
+            Check::new("cards only win copies of cards in the table", overflowing),
+        ]
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_card_from_str() {
-        let card = Card::from("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".parse::<Card>().unwrap();
 
         assert_eq!(card.id, 1);
         assert_eq!(card.winning_numbers, vec![41, 48, 83, 86, 17].into_iter().collect());
         assert_eq!(card.numbers, vec![83, 86, 6, 31, 17, 9, 48, 53]);
     }
 
+    #[test]
+    fn test_card_from_invalid_str() {
+        let err = "Card 1: 41 48 83 86 17 | 83 86  6 3l 17  9 48 53".parse::<Card>().err();
+        assert_eq!(Some(ParseError::new(1, 35, ErrorKind::InvalidNumber("3l".into()))), err);
+
+        let err = "Card 1: 41 48 83 86 17 83 86  6 31 17  9 48 53".parse::<Card>().err();
+        assert_eq!(Some(ParseError::new(1, 47, ErrorKind::MissingSeparator)), err);
+    }
+
     #[test]
     fn test_card_point_value() {
-        let card = Card::from("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53");
+        let card = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53".parse::<Card>().unwrap();
         assert_eq!(card.point_value(), Some(8));
 
-        let card = Card::from("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1");
+        let card = "Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1".parse::<Card>().unwrap();
         assert_eq!(card.point_value(), Some(2));
 
-        let card = Card::from("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83");
+        let card = "Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83".parse::<Card>().unwrap();
         assert_eq!(card.point_value(), Some(1));
 
-        let card = Card::from("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36");
+        let card = "Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36".parse::<Card>().unwrap();
         assert_eq!(card.point_value(), None);
     }
+
+    #[test]
+    fn test_expand_cards() {
+        let card_data = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+        let cards = card_data
+            .lines()
+            .map(|s| s.parse::<Card>().unwrap())
+            .collect::<Vec<Card>>();
+
+        let expanded_cards = expand_cards(&cards, &cards);
+        assert_eq!(expanded_cards.len(), 30);
+    }
+
+    #[test]
+    fn test_lint() {
+        let lint = |input: &str| Scratchcards::parse(input).unwrap().lint().into_iter().map(|check| check.violation).collect::<Vec<_>>();
+
+        assert_eq!(vec![None, None], lint("Card 1: 41 48 | 83 41\nCard 2: 13 32 | 61 30"));
+        assert_eq!(
+            vec![Some("card on line 2 has id 3".to_string()), None],
+            lint("Card 1: 41 48 | 83 86\nCard 3: 13 32 | 61 30"),
+        );
+        assert_eq!(
+            vec![None, Some("card 2 wins copies up to card 3, the last card is 2".to_string())],
+            lint("Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 32"),
+        );
+    }
 }
@@ -1,3 +1,8 @@
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::lint::{Check, Lint};
+use aoc_core::{parse, Answer, Solution};
+
 #[derive(Debug, Clone, PartialEq)]
 struct Range {
     start: i64,
@@ -35,39 +40,23 @@ impl Map {
             }
         }
 
-        if overlap_source_ranges.is_empty() {
-            result.push(range.clone());
-        } else {
-            overlap_source_ranges.sort_unstable_by_key(|r| r.start);
-            if overlap_source_ranges.len() == 1 {
-                if overlap_source_ranges[0].start > range.start {
-                    result.push(Range {
-                        start: range.start,
-                        end: overlap_source_ranges[0].start - 1,
-                    });
-                }
-                if overlap_source_ranges[0].end < range.end {
-                    result.push(Range {
-                        start: overlap_source_ranges[0].end + 1,
-                        end: range.end,
-                    });
-                }
-            } else {
-                for overlap_source_range in overlap_source_ranges.windows(2) {
-                    if overlap_source_range[0].start > range.start {
-                        result.push(Range {
-                            start: range.start,
-                            end: overlap_source_range[0].start - 1,
-                        });
-                    }
-                    if overlap_source_range[0].end + 1 < overlap_source_range[1].start {
-                        result.push(Range {
-                            start: overlap_source_range[0].end + 1,
-                            end: overlap_source_range[1].start - 1,
-                        });
-                    }
-                }
+        // The parts of the range outside of every mapping keep their values.
+        overlap_source_ranges.sort_unstable_by_key(|r| r.start);
+        let mut unmapped_start = range.start;
+        for overlap_source_range in &overlap_source_ranges {
+            if overlap_source_range.start > unmapped_start {
+                result.push(Range {
+                    start: unmapped_start,
+                    end: overlap_source_range.start - 1,
+                });
             }
+            unmapped_start = overlap_source_range.end + 1;
+        }
+        if unmapped_start <= range.end {
+            result.push(Range {
+                start: unmapped_start,
+                end: range.end,
+            });
         }
 
         result
@@ -79,21 +68,61 @@ pub struct Almanac {
     maps: Vec<Map>,
 }
 
-impl From<&str> for Almanac {
-    fn from(s: &str) -> Self {
-        let mut lines = s.lines();
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    MissingSeeds,
+    MissingSeedRangeLength,
+    MissingMappingValue,
+    InvalidNumber(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingSeeds => write!(f, "expected \"seeds: \" followed by the seed ranges"),
+            ErrorKind::MissingSeedRangeLength => write!(f, "missing length of the last seed range"),
+            ErrorKind::MissingMappingValue => write!(f, "expected destination start, source start and range length"),
+            ErrorKind::InvalidNumber(s) => write!(f, "invalid number {:?}", s),
+        }
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+fn error(line_number: usize, line: &str, token: &str, kind: ErrorKind) -> ParseError {
+    ParseError::new(line_number, parse::column(line, token), kind)
+}
+
+fn parse_number(line_number: usize, line: &str, token: &str) -> Result<i64, ParseError> {
+    token
+        .parse::<i64>()
+        .map_err(|_| error(line_number, line, token, ErrorKind::InvalidNumber(token.to_string())))
+}
+
+impl FromStr for Almanac {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(i, line)| (i + 1, line));
 
         let mut seeds = Vec::new();
-        let seeds_line = lines.next().unwrap().split_once(": ").unwrap().1;
+        let (_, first_line) = lines.next().ok_or(ParseError::new(1, 1, ErrorKind::MissingSeeds))?;
+        let seeds_line = first_line
+            .split_once(": ")
+            .ok_or_else(|| error(1, first_line, first_line, ErrorKind::MissingSeeds))?
+            .1;
         for pair in seeds_line.split_whitespace().collect::<Vec<&str>>().chunks(2) {
-            let start = pair[0].parse::<i64>().unwrap();
-            let end = start + pair[1].parse::<i64>().unwrap() - 1;
+            if pair.len() < 2 {
+                return Err(error(1, first_line, &first_line[first_line.len()..], ErrorKind::MissingSeedRangeLength));
+            }
+            let start = parse_number(1, first_line, pair[0])?;
+            let end = start + parse_number(1, first_line, pair[1])? - 1;
             seeds.push(Range { start, end });
         }
 
         let mut maps = Vec::new();
         let mut mappings = Vec::new();
-        while let Some(line) = lines.next() {
+        for (line_number, line) in lines {
             if line.contains("map") {
                 if !mappings.is_empty() {
                     maps.push(Map { mappings });
@@ -101,9 +130,13 @@ impl From<&str> for Almanac {
                 }
             } else if !line.trim().is_empty() {
                 let mut entries = line.split_whitespace();
-                let destination_range_start = entries.next().unwrap().parse::<i64>().unwrap();
-                let source_range_start = entries.next().unwrap().parse::<i64>().unwrap();
-                let range_length = entries.next().unwrap().parse::<i64>().unwrap();
+                let mut next_number = || match entries.next() {
+                    Some(entry) => parse_number(line_number, line, entry),
+                    None => Err(error(line_number, line, &line[line.len()..], ErrorKind::MissingMappingValue)),
+                };
+                let destination_range_start = next_number()?;
+                let source_range_start = next_number()?;
+                let range_length = next_number()?;
 
                 let mapping = Mapping {
                     source: Range {
@@ -119,18 +152,34 @@ impl From<&str> for Almanac {
             maps.push(Map { mappings });
         }
 
-        Almanac {
+        Ok(Almanac {
             seeds,
             maps,
-        }
+        })
     }
 }
 
 impl Almanac {
+    // In part 1 the numbers of the seeds line are seeds of their own, rather
+    // than starts and lengths of ranges of seeds.
+    pub fn find_lowest_location_of_single_seeds(&self) -> i64 {
+        let seeds = self.seeds
+            .iter()
+            .flat_map(|seed| [seed.start, seed.end - seed.start + 1])
+            .map(|seed| Range { start: seed, end: seed })
+            .collect::<Vec<_>>();
+
+        self.lowest_location(&seeds)
+    }
+
     pub fn find_lowest_location(&self) -> i64 {
+        self.lowest_location(&self.seeds)
+    }
+
+    fn lowest_location(&self, seeds: &[Range]) -> i64 {
         let mut lowest_location = i64::MAX;
 
-        for seed in &self.seeds {
+        for seed in seeds {
             let mut ranges = vec![seed.clone()];
 
             for map in &self.maps {
@@ -152,10 +201,58 @@ impl Almanac {
     }
 }
 
-#[cfg(test)]
+impl Solution for Almanac {
+    type Error = ParseError;
 
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+
+    fn part1(&mut self) -> Answer {
+        self.find_lowest_location_of_single_seeds().into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        self.find_lowest_location().into()
+    }
+}
+
+impl Lint for Almanac {
+    fn lint(&self) -> Vec<Check> {
+        let empty = self.seeds
+            .iter()
+            .position(|seed| seed.end < seed.start)
+            .map(|i| format!("seed range {} is empty", i + 1))
+            .or_else(|| {
+                self.maps.iter().enumerate().find_map(|(i, map)| {
+                    let j = map.mappings.iter().position(|mapping| mapping.source.end < mapping.source.start)?;
+                    Some(format!("mapping {} of map {} is empty", j + 1, i + 1))
+                })
+            });
+
+        // `Map::apply` maps every overlapping part of a range, once per mapping.
+        let overlapping = self.maps.iter().enumerate().find_map(|(i, map)| {
+            let mut sources = map.mappings.iter().enumerate().collect::<Vec<_>>();
+            sources.sort_by_key(|(_, mapping)| mapping.source.start);
+            sources.windows(2).find_map(|pair| {
+                let ((a, first), (b, second)) = (pair[0], pair[1]);
+                (second.source.start <= first.source.end).then(|| {
+                    format!("mappings {} and {} of map {} overlap", a.min(b) + 1, a.max(b) + 1, i + 1)
+                })
+            })
+        });
+
+        vec![
+            Check::new("seed ranges and mappings have a positive length", empty),
+            Check::new("mappings within a map do not overlap", overlapping),
+        ]
+    }
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_map_apply() {
@@ -214,6 +311,13 @@ mod tests {
         assert_eq!(result[2].start, 11);
         assert_eq!(result[2].end, 14);
 
+        // range extending past multiple mappings
+        let range = Range { start: 7, end: 25 };
+        let result = map.apply(&range);
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[2], Range { start: 11, end: 14 });
+        assert_eq!(result[3], Range { start: 21, end: 25 });
+
         // no overlap
         let map = Map {
             mappings: vec![
@@ -243,7 +347,7 @@ soil-to-fertilizer map:
 0 15 37
 37 52 2
 39 0 15";
-        let almanac = Almanac::from(input);
+        let almanac = input.parse::<Almanac>().unwrap();
 
         assert_eq!(almanac.seeds.len(), 2);
         assert_eq!(almanac.seeds[0].start, 79);
@@ -257,6 +361,34 @@ soil-to-fertilizer map:
         assert_eq!(almanac.maps[0].mappings[0].delta, -48);
     }
 
+    #[test]
+    fn test_almanac_from_invalid_str() {
+        let input = "\
+seeds: 79 14 55
+
+seed-to-soil map:
+50 98 2";
+        let err = input.parse::<Almanac>().err();
+        assert_eq!(Some(ParseError::new(1, 16, ErrorKind::MissingSeedRangeLength)), err);
+
+        let input = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 5O 48";
+        let err = input.parse::<Almanac>().err();
+        assert_eq!(Some(ParseError::new(5, 4, ErrorKind::InvalidNumber("5O".into()))), err);
+
+        let input = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98";
+        let err = input.parse::<Almanac>().err();
+        assert_eq!(Some(ParseError::new(4, 6, ErrorKind::MissingMappingValue)), err);
+    }
+
     #[test]
     fn test_find_lowest_location() {
         let input = "\
@@ -293,8 +425,86 @@ temperature-to-humidity map:
 humidity-to-location map:
 60 56 37
 56 93 4";
-        let almanac = Almanac::from(input);
+        let almanac = input.parse::<Almanac>().unwrap();
 
+        assert_eq!(almanac.find_lowest_location_of_single_seeds(), 35);
         assert_eq!(almanac.find_lowest_location(), 46);
     }
+
+    #[test]
+    fn test_lint() {
+        let lint = |input: &str| input.parse::<Almanac>().unwrap().lint().into_iter().map(|check| check.violation).collect::<Vec<_>>();
+
+        assert_eq!(vec![None, None], lint("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n"));
+        assert_eq!(
+            vec![None, Some("mappings 1 and 3 of map 2 overlap".to_string())],
+            lint("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-fertilizer map:\n0 15 37\n37 52 2\n39 50 3\n"),
+        );
+        assert_eq!(vec![Some("seed range 2 is empty".to_string()), None], lint("seeds: 79 14 55 0\n"));
+        assert_eq!(
+            vec![Some("mapping 2 of map 1 is empty".to_string()), None],
+            lint("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 0\n"),
+        );
+    }
+
+    // Mappings with disjoint source ranges, as in the puzzle input.
+    fn map(mappings: Vec<(i64, i64, i64)>) -> Map {
+        let mut map = Map { mappings: Vec::new() };
+
+        for (start, length, delta) in mappings {
+            let source = Range { start, end: start + length - 1 };
+            if map.mappings.iter().all(|m| source.end < m.source.start || m.source.end < source.start) {
+                map.mappings.push(Mapping { source, delta });
+            }
+        }
+
+        map
+    }
+
+    fn mappings() -> impl Strategy<Value = Vec<(i64, i64, i64)>> {
+        prop::collection::vec((0i64..100, 1i64..20, -50i64..50), 0..6)
+    }
+
+    // Reference implementation mapping a single value.
+    fn map_value(map: &Map, value: i64) -> i64 {
+        match map.mappings.iter().find(|m| m.source.start <= value && value <= m.source.end) {
+            Some(mapping) => value + mapping.delta,
+            None => value,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_map_apply_matches_mapping_every_value(mappings in mappings(), start in 0i64..120, length in 1i64..40) {
+            let map = map(mappings);
+            let range = Range { start, end: start + length - 1 };
+
+            let mut expected = (range.start..=range.end).map(|value| map_value(&map, value)).collect::<Vec<_>>();
+            let mut mapped = map.apply(&range).iter().flat_map(|r| r.start..=r.end).collect::<Vec<_>>();
+            expected.sort_unstable();
+            mapped.sort_unstable();
+
+            prop_assert_eq!(expected, mapped);
+        }
+
+        #[test]
+        fn test_find_lowest_location_matches_mapping_every_seed(
+            maps in prop::collection::vec(mappings(), 1..4),
+            seeds in prop::collection::vec((0i64..120, 1i64..30), 1..4),
+        ) {
+            let almanac = Almanac {
+                seeds: seeds.iter().map(|&(start, length)| Range { start, end: start + length - 1 }).collect(),
+                maps: maps.into_iter().map(map).collect(),
+            };
+
+            let expected = almanac.seeds
+                .iter()
+                .flat_map(|seed| seed.start..=seed.end)
+                .map(|seed| almanac.maps.iter().fold(seed, |value, map| map_value(map, value)))
+                .min()
+                .unwrap();
+
+            prop_assert_eq!(expected, almanac.find_lowest_location());
+        }
+    }
 }
@@ -1,6 +1,9 @@
+use aoc_core::{input, parse};
 use day_5::almanac;
 
 fn main() {
-    let almanac = almanac::Almanac::from(include_str!("../input.txt"));
-    println!("Lowest location number: {}", almanac.find_lowest_location());
+    let input = input::from_args_or_exit(5);
+    let almanac = parse::or_exit(5, input.parse::<almanac::Almanac>());
+    println!("Lowest location number: {}", almanac.find_lowest_location_of_single_seeds());
+    println!("Lowest location number of the seed ranges: {}", almanac.find_lowest_location());
 }
@@ -1,14 +1,18 @@
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::{math, parse, Answer, Solution};
+
 #[derive(Debug)]
 pub struct Race {
-    max_time: f64,
-    best_distance: f64,
+    max_time: u64,
+    best_distance: u64,
 }
 
 impl Race {
     pub fn new(max_time: u64, best_distance: u64) -> Self {
         Self {
-            max_time: max_time as f64,
-            best_distance: best_distance as f64,
+            max_time,
+            best_distance,
         }
     }
 
@@ -23,25 +27,141 @@ impl Race {
     there are floor(n2) - ceil(n1) + 1 possible button held times
     where it'd beat best distance (because the parabola is concave).
 
+    To stay exact for large races everything is done in integers: with the integer square root
+    of the discriminant, (max_time - isqrt) / 2 is at most one below the first beating held time,
+    and since the parabola is symmetric around max_time / 2 the last one is max_time - first.
+    A negative discriminant means the record is beyond any distance, so no held time beats it.
+
     For visual see: parabola.png in the root of the project.
      */
-    pub fn number_of_beating_held_times(&self) -> u32 {
-        let discriminant = self.max_time.powf(2.0) - 4.0 * self.best_distance;
-        if discriminant < 0.0 {
-            panic!("No possible button held time");
+    pub fn number_of_beating_held_times(&self) -> u64 {
+        let max_time = self.max_time as u128;
+        let best_distance = self.best_distance as u128;
+
+        let Some(discriminant) = (max_time * max_time).checked_sub(4 * best_distance) else {
+            return 0;
+        };
+
+        let beats = |n: u128| n * (max_time - n) > best_distance;
+
+        let mut first = (max_time - math::isqrt(discriminant)) / 2;
+        while first <= max_time / 2 && !beats(first) {
+            first += 1;
+        }
+        if first > max_time / 2 {
+            return 0;
         }
 
-        let sqrt_discriminant = discriminant.sqrt();
-        let n1 = (self.max_time - sqrt_discriminant) / 2.0;
-        let n2 = (self.max_time + sqrt_discriminant) / 2.0;
+        // At most max_time - 1, as holding for 0 never beats the record.
+        (max_time - 2 * first + 1) as u64
+    }
+}
+
+pub struct Races {
+    races: Vec<Race>,
+    // The single race obtained by ignoring the spaces between the numbers.
+    race: Race,
+}
 
-        ((n2 - 1.0).ceil() - (n1 + 1.0).floor() + 1.0) as u32
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    MissingTimes,
+    MissingDistances,
+    MismatchedCount,
+    InvalidNumber(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingTimes => write!(f, "expected \"Time:\" followed by the race times"),
+            ErrorKind::MissingDistances => write!(f, "expected \"Distance:\" followed by the record distances"),
+            ErrorKind::MismatchedCount => write!(f, "the number of distances differs from the number of times"),
+            ErrorKind::InvalidNumber(s) => write!(f, "invalid number {:?}", s),
+        }
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+fn parse_number(line_number: usize, line: &str, token: &str) -> Result<u64, ParseError> {
+    token
+        .replace(' ', "")
+        .parse::<u64>()
+        .map_err(|_| ParseError::new(line_number, parse::column(line, token), ErrorKind::InvalidNumber(token.to_string())))
+}
+
+impl FromStr for Races {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut lines = input.lines();
+        let times_line = lines.next().unwrap_or_default();
+        let distances_line = lines.next().unwrap_or_default();
+
+        let times_str = times_line
+            .split_once(':')
+            .ok_or(ParseError::new(1, 1, ErrorKind::MissingTimes))?
+            .1
+            .trim();
+        let distances_str = distances_line
+            .split_once(':')
+            .ok_or(ParseError::new(2, 1, ErrorKind::MissingDistances))?
+            .1
+            .trim();
+
+        let times = times_str.split_whitespace().collect::<Vec<_>>();
+        let distances = distances_str.split_whitespace().collect::<Vec<_>>();
+        if times.len() != distances.len() {
+            return Err(ParseError::new(2, distances_line.len() + 1, ErrorKind::MismatchedCount));
+        }
+
+        let mut races = Vec::new();
+        for (time_str, distance_str) in times.into_iter().zip(distances) {
+            let max_time = parse_number(1, times_line, time_str)?;
+            let best_distance = parse_number(2, distances_line, distance_str)?;
+
+            races.push(Race::new(max_time, best_distance));
+        }
+
+        let race = Race::new(
+            parse_number(1, times_line, times_str)?,
+            parse_number(2, distances_line, distances_str)?,
+        );
+
+        Ok(Self { races, race })
+    }
+}
+
+impl Races {
+    pub fn margin_of_error(&self) -> u64 {
+        self.races
+            .iter()
+            .map(|race| race.number_of_beating_held_times())
+            .product()
+    }
+}
+
+impl Solution for Races {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+
+    fn part1(&mut self) -> Answer {
+        self.margin_of_error().into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        self.race.number_of_beating_held_times().into()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_number_of_beating_held_times() {
@@ -57,12 +177,64 @@ mod tests {
         let race = Race::new(71530, 940200);
         assert_eq!(71503, race.number_of_beating_held_times());
 
+        // The record is only matched, never beaten.
+        let race = Race::new(4, 4);
+        assert_eq!(0, race.number_of_beating_held_times());
+
+        // Large enough for f64 to lose precision on the discriminant.
+        let race = Race::new(1_000_000_001, 250_000_000_499_999_999);
+        assert_eq!(2, race.number_of_beating_held_times());
+
+        // The record is beyond any distance.
+        let race = Race::new(7, 900);
+        assert_eq!(0, race.number_of_beating_held_times());
+
+        // More ways to win than a u32 holds.
+        let race = Race::new(10_000_000_000, 1);
+        assert_eq!(9_999_999_999, race.number_of_beating_held_times());
     }
 
     #[test]
-    #[should_panic]
-    fn test_number_of_beating_held_times_panics() {
-        let race = Race::new(7, 900000);
-        let _ = race.number_of_beating_held_times();
+    fn test_races_margin_of_error() {
+        let input = "\
+Time:      7  15   30
+Distance:  9  40  200";
+        let races = input.parse::<Races>().unwrap();
+
+        assert_eq!(3, races.races.len());
+        assert_eq!(288, races.margin_of_error());
+        assert_eq!(71503, races.race.number_of_beating_held_times());
+    }
+
+    #[test]
+    fn test_races_from_invalid_str() {
+        let input = "\
+Time:      7  15   30
+Distance:  9  4O  200";
+        let err = input.parse::<Races>().err();
+        assert_eq!(Some(ParseError::new(2, 15, ErrorKind::InvalidNumber("4O".into()))), err);
+
+        let input = "Time:      7  15   30";
+        let err = input.parse::<Races>().err();
+        assert_eq!(Some(ParseError::new(2, 1, ErrorKind::MissingDistances)), err);
+    }
+
+    // Reference implementation trying every held time.
+    fn brute_force_number_of_beating_held_times(race: &Race) -> u64 {
+        (0..=race.max_time)
+            .filter(|held| held * (race.max_time - held) > race.best_distance)
+            .count() as u64
+    }
+
+    proptest! {
+        #[test]
+        fn test_number_of_beating_held_times_matches_brute_force(
+            // Records beyond max_time² / 4 cannot even be matched, half of them are.
+            (max_time, best_distance) in (0u64..2000).prop_flat_map(|t| (Just(t), 0..=t * t / 2)),
+        ) {
+            let race = Race::new(max_time, best_distance);
+
+            prop_assert_eq!(brute_force_number_of_beating_held_times(&race), race.number_of_beating_held_times());
+        }
     }
 }
@@ -1,22 +1,26 @@
 use std::collections::HashMap;
 use itertools::Itertools;
 use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::{parse, Answer, Solution};
 
 #[derive(PartialEq, Eq, Hash, Clone, PartialOrd, Debug)]
 struct Card(u8);
 
 impl Card {
-    fn new(symbol: char) -> Card {
+    fn new(symbol: char) -> Option<Card> {
         let value = match symbol {
             'T' => 10,
             'J' => 1,
             'Q' => 12,
             'K' => 13,
             'A' => 14,
-            _ => symbol.to_digit(10).unwrap() as u8,
+            '2'..='9' => symbol.to_digit(10)? as u8,
+            _ => return None,
         };
 
-        Card(value)
+        Some(Card(value))
     }
 }
 
@@ -54,10 +58,17 @@ impl Hand {
         Hand {
             cards,
             category,
-            bid: bid,
+            bid,
         }
     }
 
+    // In part 1 J is a jack, between T and Q, rather than a joker.
+    fn without_jokers(&self) -> Hand {
+        let cards = self.cards.clone().map(|card| if card.0 == 1 { Card(11) } else { card });
+
+        Hand::new(cards, self.bid)
+    }
+
     fn determine_category(cards: &[Card; 5]) -> Category {
         let number_of_jokers = cards.iter().filter(|card| card.0 == 1).count() as u8;
         if number_of_jokers == 5 {
@@ -78,7 +89,7 @@ impl Hand {
                 stats
             })
             .values()
-            .map(|&count| count)
+            .copied()
             .sorted()
             .collect();
 
@@ -111,25 +122,62 @@ pub struct CamelCards {
     hands: Vec<Hand>,
 }
 
-impl From<&str> for CamelCards {
-    fn from(input: &str) -> Self {
-        let mut hands = Vec::new();
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    MissingBid,
+    InvalidCard(char),
+    InvalidHandSize(usize),
+    InvalidBid(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingBid => write!(f, "expected a hand followed by a bid"),
+            ErrorKind::InvalidCard(symbol) => write!(f, "invalid card {:?}", symbol),
+            ErrorKind::InvalidHandSize(size) => write!(f, "a hand has 5 cards, found {}", size),
+            ErrorKind::InvalidBid(s) => write!(f, "invalid bid {:?}", s),
+        }
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
 
-        for line in input.lines() {
-            let (hand_str, bid_str) = line.split_whitespace().collect_tuple().unwrap();
+impl FromStr for Hand {
+    type Err = ParseError;
 
-            let cards: [Card; 5] = hand_str
-                .chars()
-                .map(|symbol| Card::new(symbol))
-                .collect::<Vec<Card>>()
-                .try_into()
-                .unwrap();
-            let bid = bid_str.parse::<u32>().unwrap();
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let error = |token: &str, kind| ParseError::new(1, parse::column(line, token), kind);
 
-            hands.push(Hand::new(cards, bid));
+        let (hand_str, bid_str) = line
+            .split_whitespace()
+            .collect_tuple()
+            .ok_or_else(|| error(&line[line.len()..], ErrorKind::MissingBid))?;
+
+        let mut cards = Vec::new();
+        for (i, symbol) in hand_str.char_indices() {
+            let card = Card::new(symbol).ok_or_else(|| error(&hand_str[i..], ErrorKind::InvalidCard(symbol)))?;
+            cards.push(card);
         }
+        let size = cards.len();
+        let cards: [Card; 5] = cards
+            .try_into()
+            .map_err(|_| error(hand_str, ErrorKind::InvalidHandSize(size)))?;
+        let bid = bid_str
+            .parse::<u32>()
+            .map_err(|_| error(bid_str, ErrorKind::InvalidBid(bid_str.to_string())))?;
+
+        Ok(Hand::new(cards, bid))
+    }
+}
 
-        CamelCards { hands }
+impl FromStr for CamelCards {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let hands = parse::lines(input, str::parse)?;
+
+        Ok(CamelCards { hands })
     }
 }
 
@@ -142,15 +190,35 @@ impl CamelCards {
         self.rank_hands();
 
         let mut total_winnings = 0;
-        let mut multiplier = 1;
 
-        for hand in self.hands.iter() {
-            total_winnings += hand.bid * multiplier;
-            multiplier += 1;
+        for (rank, hand) in self.hands.iter().enumerate() {
+            total_winnings += hand.bid * (rank as u32 + 1);
         }
 
         total_winnings
     }
+
+    pub fn total_winnings_without_jokers(&self) -> u32 {
+        let mut camel_cards = CamelCards { hands: self.hands.iter().map(Hand::without_jokers).collect() };
+
+        camel_cards.total_winnings()
+    }
+}
+
+impl Solution for CamelCards {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+
+    fn part1(&mut self) -> Answer {
+        self.total_winnings_without_jokers().into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        self.total_winnings().into()
+    }
 }
 
 #[cfg(test)]
@@ -160,16 +228,19 @@ mod tests {
     #[test]
     fn test_card_new() {
         let card = Card::new('T');
-        assert_eq!(card, Card(10));
+        assert_eq!(card, Some(Card(10)));
 
         let card = Card::new('J');
-        assert_eq!(card, Card(1));
+        assert_eq!(card, Some(Card(1)));
 
         let card = Card::new('A');
-        assert_eq!(card, Card(14));
+        assert_eq!(card, Some(Card(14)));
 
         let card = Card::new('2');
-        assert_eq!(card, Card(2));
+        assert_eq!(card, Some(Card(2)));
+
+        let card = Card::new('1');
+        assert_eq!(card, None);
     }
 
     #[test]
@@ -185,71 +256,71 @@ mod tests {
     #[test]
     fn test_hand_new() {
         let cards = [
-            Card::new('2'),
-            Card::new('3'),
-            Card::new('4'),
-            Card::new('5'),
-            Card::new('6'),
+            Card::new('2').unwrap(),
+            Card::new('3').unwrap(),
+            Card::new('4').unwrap(),
+            Card::new('5').unwrap(),
+            Card::new('6').unwrap(),
         ];
         let hand = Hand::new(cards, 1);
         assert_eq!(Category::HighCard, hand.category);
 
         let cards = [
-            Card::new('A'),
-            Card::new('2'),
-            Card::new('3'),
-            Card::new('A'),
-            Card::new('4'),
+            Card::new('A').unwrap(),
+            Card::new('2').unwrap(),
+            Card::new('3').unwrap(),
+            Card::new('A').unwrap(),
+            Card::new('4').unwrap(),
         ];
         let hand = Hand::new(cards, 1);
         assert_eq!(Category::OnePair, hand.category);
 
         let cards = [
-            Card::new('2'),
-            Card::new('3'),
-            Card::new('4'),
-            Card::new('3'),
-            Card::new('2'),
+            Card::new('2').unwrap(),
+            Card::new('3').unwrap(),
+            Card::new('4').unwrap(),
+            Card::new('3').unwrap(),
+            Card::new('2').unwrap(),
         ];
         let hand = Hand::new(cards, 1);
         assert_eq!(Category::TwoPair, hand.category);
 
         let cards = [
-            Card::new('T'),
-            Card::new('T'),
-            Card::new('T'),
-            Card::new('9'),
-            Card::new('8'),
+            Card::new('T').unwrap(),
+            Card::new('T').unwrap(),
+            Card::new('T').unwrap(),
+            Card::new('9').unwrap(),
+            Card::new('8').unwrap(),
         ];
         let hand = Hand::new(cards, 1);
         assert_eq!(Category::ThreeOfAKind, hand.category);
 
         let cards = [
-            Card::new('2'),
-            Card::new('3'),
-            Card::new('3'),
-            Card::new('3'),
-            Card::new('2'),
+            Card::new('2').unwrap(),
+            Card::new('3').unwrap(),
+            Card::new('3').unwrap(),
+            Card::new('3').unwrap(),
+            Card::new('2').unwrap(),
         ];
         let hand = Hand::new(cards, 1);
         assert_eq!(Category::FullHouse, hand.category);
 
         let cards = [
-            Card::new('A'),
-            Card::new('A'),
-            Card::new('8'),
-            Card::new('A'),
-            Card::new('A'),
+            Card::new('A').unwrap(),
+            Card::new('A').unwrap(),
+            Card::new('8').unwrap(),
+            Card::new('A').unwrap(),
+            Card::new('A').unwrap(),
         ];
         let hand = Hand::new(cards, 1);
         assert_eq!(Category::FourOfAKind, hand.category);
 
         let cards = [
-            Card::new('A'),
-            Card::new('A'),
-            Card::new('A'),
-            Card::new('A'),
-            Card::new('A'),
+            Card::new('A').unwrap(),
+            Card::new('A').unwrap(),
+            Card::new('A').unwrap(),
+            Card::new('A').unwrap(),
+            Card::new('A').unwrap(),
         ];
         let hand = Hand::new(cards, 1);
         assert_eq!(Category::FiveOfAKind, hand.category);
@@ -259,21 +330,21 @@ mod tests {
     fn test_hand_compare_different_categories() {
         let lower_hand = Hand::new(
             [
-                Card::new('2'),
-                Card::new('3'),
-                Card::new('4'),
-                Card::new('5'),
-                Card::new('6'),
+                Card::new('2').unwrap(),
+                Card::new('3').unwrap(),
+                Card::new('4').unwrap(),
+                Card::new('5').unwrap(),
+                Card::new('6').unwrap(),
             ],
             50,
         );
         let higher_hand = Hand::new(
             [
-                Card::new('A'),
-                Card::new('2'),
-                Card::new('3'),
-                Card::new('A'),
-                Card::new('4'),
+                Card::new('A').unwrap(),
+                Card::new('2').unwrap(),
+                Card::new('3').unwrap(),
+                Card::new('A').unwrap(),
+                Card::new('4').unwrap(),
             ],
             50,
         );
@@ -284,21 +355,21 @@ mod tests {
     fn test_hand_compare_same_categories() {
         let lower_hand = Hand::new(
             [
-                Card::new('2'),
-                Card::new('3'),
-                Card::new('4'),
-                Card::new('5'),
-                Card::new('6'),
+                Card::new('2').unwrap(),
+                Card::new('3').unwrap(),
+                Card::new('4').unwrap(),
+                Card::new('5').unwrap(),
+                Card::new('6').unwrap(),
             ],
             50,
         );
         let higher_hand = Hand::new(
             [
-                Card::new('2'),
-                Card::new('3'),
-                Card::new('4'),
-                Card::new('5'),
-                Card::new('7'),
+                Card::new('2').unwrap(),
+                Card::new('3').unwrap(),
+                Card::new('4').unwrap(),
+                Card::new('5').unwrap(),
+                Card::new('7').unwrap(),
             ],
             50,
         );
@@ -309,21 +380,21 @@ mod tests {
     fn test_hand_compare_with_jokers() {
         let lower_hand = Hand::new(
             [
-                Card::new('Q'),
-                Card::new('Q'),
-                Card::new('Q'),
-                Card::new('J'),
-                Card::new('A'),
+                Card::new('Q').unwrap(),
+                Card::new('Q').unwrap(),
+                Card::new('Q').unwrap(),
+                Card::new('J').unwrap(),
+                Card::new('A').unwrap(),
             ],
             50,
         );
         let higher_hand = Hand::new(
             [
-                Card::new('K'),
-                Card::new('T'),
-                Card::new('J'),
-                Card::new('J'),
-                Card::new('T'),
+                Card::new('K').unwrap(),
+                Card::new('T').unwrap(),
+                Card::new('J').unwrap(),
+                Card::new('J').unwrap(),
+                Card::new('T').unwrap(),
             ],
             50,
         );
@@ -338,13 +409,30 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        let camel_cards = CamelCards::from(input);
+        let camel_cards = input.parse::<CamelCards>().unwrap();
 
         assert_eq!(camel_cards.hands.len(), 5);
         assert_eq!(camel_cards.hands[0].cards, [Card(3), Card(2), Card(10), Card(3), Card(13)]);
         assert_eq!(camel_cards.hands[0].bid, 765);
     }
 
+    #[test]
+    fn test_camel_cards_from_invalid_str() {
+        let input = "\
+32T3K 765
+T55X5 684";
+        let err = input.parse::<CamelCards>().err();
+        assert_eq!(Some(ParseError::new(2, 4, ErrorKind::InvalidCard('X'))), err);
+
+        let input = "32T3 765";
+        let err = input.parse::<CamelCards>().err();
+        assert_eq!(Some(ParseError::new(1, 1, ErrorKind::InvalidHandSize(4))), err);
+
+        let input = "32T3K";
+        let err = input.parse::<CamelCards>().err();
+        assert_eq!(Some(ParseError::new(1, 6, ErrorKind::MissingBid)), err);
+    }
+
     #[test]
     fn test_camel_cards_total_winnings() {
         let input = "\
@@ -353,8 +441,9 @@ T55J5 684
 KK677 28
 KTJJT 220
 QQQJA 483";
-        let mut camel_cards = CamelCards::from(input);
+        let mut camel_cards = input.parse::<CamelCards>().unwrap();
 
+        assert_eq!(camel_cards.total_winnings_without_jokers(), 6440);
         assert_eq!(camel_cards.total_winnings(), 5905);
     }
 }
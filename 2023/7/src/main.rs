@@ -1,7 +1,9 @@
+use aoc_core::{input, parse};
 use day_7::camel_cards;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let mut camel_cards = camel_cards::CamelCards::from(input);
-    println!("Part 1: {}", camel_cards.total_winnings());
+    let input = input::from_args_or_exit(7);
+    let mut camel_cards = parse::or_exit(7, input.parse::<camel_cards::CamelCards>());
+    println!("Part 1: {}", camel_cards.total_winnings_without_jokers());
+    println!("Part 2: {}", camel_cards.total_winnings());
 }
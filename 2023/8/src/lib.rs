@@ -1,2 +1 @@
 pub mod network;
-pub mod utils;
@@ -1,8 +1,10 @@
+use aoc_core::{input, parse};
 use day_8::network;
 
 fn main() {
-    let input = include_str!("../input.txt");
-    let network = network::Network::from(input);
+    let input = input::from_args_or_exit(8);
+    let network = parse::or_exit(8, input.parse::<network::Network>());
     //println!("Distance from AA to ZZ: {}", network.distance(network::Element("AAA".into()), network::Element("ZZZ".into())).unwrap());
-    println!("Part 2: {}", network.distance_from_as_to_zs());
+    let steps = parse::or_exit(8, network.distance_from_as_to_zs().ok_or("the ghosts are never all on nodes ending with Z at once"));
+    println!("Part 2: {}", steps);
 }
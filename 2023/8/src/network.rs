@@ -1,6 +1,9 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use rayon::prelude::*;
-use crate::utils;
+use aoc_core::lint::{Check, Lint};
+use aoc_core::{math, parse, Answer, Solution};
 
 #[derive(Eq, Hash, PartialEq, Clone, Debug)]
 pub struct Element(pub String);
@@ -19,6 +22,44 @@ pub struct Network {
     turns: Vec<Turn>,
 }
 
+// The walk from a node following the turns, as `(step, node)` with the
+// starting node at step 0. It ends after an undefined node, or once back on
+// a node at the same point of the turns, the walk repeating itself from
+// there, `loop_start` then being the step it was first on that node.
+struct Steps<'a> {
+    network: &'a Network,
+    current: Option<&'a Element>,
+    step: usize,
+    seen: HashMap<(&'a Element, usize), usize>,
+    loop_start: Option<usize>,
+}
+
+impl<'a> Iterator for Steps<'a> {
+    type Item = (usize, &'a Element);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        let turn = self.step % self.network.turns.len().max(1);
+        if let Some(&loop_start) = self.seen.get(&(current, turn)) {
+            self.loop_start = Some(loop_start);
+            self.current = None;
+            return None;
+        }
+        self.seen.insert((current, turn), self.step);
+        let item = (self.step, current);
+
+        let node = self.network.node_by_element.get(current);
+        self.current = match (node, self.network.turns.get(turn)) {
+            (Some(node), Some(Turn::Left)) => Some(&node.0),
+            (Some(node), Some(Turn::Right)) => Some(&node.1),
+            _ => None,
+        };
+        self.step += 1;
+
+        Some(item)
+    }
+}
+
 impl Network {
     fn new() -> Self {
         Self {
@@ -31,25 +72,17 @@ impl Network {
         self.node_by_element.insert(element, node);
     }
 
-    pub fn distance(&self, from: Element, to: Element) -> Option<usize> {
-        let mut distance = 0;
-        let mut current = from;
-        loop {
-            let node = self.node_by_element.get(&current)?;
-
-            let next = match self.turns.get(distance % self.turns.len()) {
-                Some(Turn::Left) => &node.0,
-                Some(Turn::Right) => &node.1,
-                None => return None,
-            };
-            distance += 1;
-
-            if *next == to {
-                return Some(distance);
-            }
+    fn steps<'a>(&'a self, from: &'a Element) -> Steps<'a> {
+        Steps { network: self, current: Some(from), step: 0, seen: HashMap::new(), loop_start: None }
+    }
 
-            current = next.clone();
-        }
+    // None when `to` cannot be reached from `from`, be it because the walk
+    // leads to an undefined node or because it loops without reaching `to`.
+    pub fn distance(&self, from: Element, to: Element) -> Option<usize> {
+        self.steps(&from)
+            .skip(1)
+            .find(|(_, element)| **element == to)
+            .map(|(step, _)| step)
     }
 
     // Given the inherent assumption in the problem statement
@@ -57,80 +90,216 @@ impl Network {
     // continue to loop until all reaches to a Z element we can deduce that the paths repeat
     // and that there's cycle. So we can find the period of each cycle and then their LCM would
     // be the number of steps to have all paths reach to Z.
-    fn find_z_period(&self, from: &Element) -> usize {
-        let mut distance = 0;
-        let mut current = from.clone();
-        loop {
-            let node = self.node_by_element.get(&current).unwrap();
-
-            let next = match self.turns.get(distance % self.turns.len()) {
-                Some(Turn::Left) => &node.0,
-                Some(Turn::Right) => &node.1,
-                None => panic!("No turns"),
-            };
-            distance += 1;
-
-            if next.0.ends_with('Z') {
-                return distance;
-            }
-
-            current = next.clone();
-        }
+    // None when the walk from `from` never gets to a node ending with Z.
+    fn find_z_period(&self, from: &Element) -> Option<usize> {
+        self.steps(from)
+            .skip(1)
+            .find(|(_, element)| element.0.ends_with('Z'))
+            .map(|(step, _)| step)
     }
 
-    pub fn distance_from_as_to_zs(&self) -> usize {
+    // None without any ghost, when a ghost never gets to a node ending with Z
+    // or when the number of steps overflows.
+    pub fn distance_from_as_to_zs(&self) -> Option<usize> {
         let periods = self.node_by_element
             .keys()
             .filter(|element| element.0.ends_with('A'))
             .collect::<Vec<_>>()
             .par_iter()
-            .map(|element| self.find_z_period(element))
-            .collect::<Vec<_>>();
+            .map(|element| self.find_z_period(element).map(|period| period as u64))
+            .collect::<Option<Vec<_>>>()?;
 
-        let mut lcm = periods[0];
-        for period in &periods[1..] {
-            lcm = utils::lcm(lcm, *period);
+        if periods.is_empty() {
+            return None;
         }
 
-        lcm
+        math::checked_lcm_of(&periods).and_then(|lcm| usize::try_from(lcm).ok())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    MissingTurns,
+    InvalidTurn(char),
+    MissingNode,
+    InvalidNode(String),
+    UnknownNode(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::MissingTurns => write!(f, "missing left/right instructions"),
+            ErrorKind::InvalidTurn(c) => write!(f, "invalid turn {:?}", c),
+            ErrorKind::MissingNode => write!(f, "expected \" = \" followed by the node"),
+            ErrorKind::InvalidNode(s) => write!(f, "invalid node {:?}, expected (LEFT, RIGHT)", s),
+            ErrorKind::UnknownNode(s) => write!(f, "node {:?} is not defined", s),
+        }
     }
 }
 
-impl From<&str> for Network {
-    fn from(input: &str) -> Self {
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+impl FromStr for Network {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
         let mut network = Network::new();
 
         let mut lines = input.lines();
 
-        let turns_str = lines.next().unwrap();
-        let turns = turns_str.chars().map(|c| match c {
-            'L' => Turn::Left,
-            'R' => Turn::Right,
-            _ => panic!("Invalid turn"),
-        });
-        network.turns.extend(turns);
+        let turns_str = lines.next().ok_or(ParseError::new(1, 1, ErrorKind::MissingTurns))?;
+        if turns_str.is_empty() {
+            return Err(ParseError::new(1, 1, ErrorKind::MissingTurns));
+        }
+        for (i, c) in turns_str.chars().enumerate() {
+            let turn = match c {
+                'L' => Turn::Left,
+                'R' => Turn::Right,
+                _ => return Err(ParseError::new(1, i + 1, ErrorKind::InvalidTurn(c))),
+            };
+            network.turns.push(turn);
+        }
 
         lines.next();
 
-        for line in lines {
-            let mut parts = line.split(" = ");
+        // Nodes may be used before they are defined, so they are only looked
+        // up once they all are.
+        let mut references = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let line_number = i + 3;
+            let error = |token: &str, kind| ParseError::new(line_number, parse::column(line, token), kind);
 
-            let element = Element(parts.next().unwrap().into());
+            let (element_str, node_str) = line
+                .split_once(" = ")
+                .ok_or_else(|| error(&line[line.len()..], ErrorKind::MissingNode))?;
 
-            let node_str = parts.next().unwrap().trim_matches(|c| c == '(' || c == ')');
-            let mut node_parts = node_str.split(", ");
-            let node = Node(Element(node_parts.next().unwrap().into()), Element(node_parts.next().unwrap().into()));
+            let element = Element(element_str.into());
 
-            network.add_node(element, node);
+            let (left, right) = node_str
+                .strip_prefix('(')
+                .and_then(|s| s.strip_suffix(')'))
+                .and_then(|s| s.split_once(", "))
+                .ok_or_else(|| error(node_str, ErrorKind::InvalidNode(node_str.to_string())))?;
+
+            for token in [left, right] {
+                references.push((line_number, parse::column(line, token), token));
+            }
+            network.add_node(element, Node(Element(left.into()), Element(right.into())));
         }
 
-        network
+        let unknown = references
+            .into_iter()
+            .find(|(_, _, name)| !network.node_by_element.contains_key(&Element(name.to_string())));
+        if let Some((line_number, column, name)) = unknown {
+            return Err(ParseError::new(line_number, column, ErrorKind::UnknownNode(name.to_string())));
+        }
+
+        Ok(network)
+    }
+}
+
+impl Solution for Network {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+
+    fn part1(&mut self) -> Answer {
+        self.distance(Element("AAA".into()), Element("ZZZ".into()))
+            .map_or(Answer::Unsolved, Answer::from)
+    }
+
+    fn part2(&mut self) -> Answer {
+        self.distance_from_as_to_zs().map_or(Answer::Unsolved, Answer::from)
+    }
+}
+
+impl Network {
+    // The steps after which the walk from `from` is on a node matching `is_end`,
+    // up to the first time the walk is back on a node at the same point of the
+    // turns, with that first step and the length of the loop from there.
+    fn walk(&self, from: &Element, is_end: impl Fn(&Element) -> bool) -> Result<(Vec<usize>, usize, usize), String> {
+        let mut steps = self.steps(from);
+        let mut ends = Vec::new();
+        let mut last = from;
+
+        for (step, element) in steps.by_ref() {
+            if step > 0 && is_end(element) {
+                ends.push(step);
+            }
+            last = element;
+        }
+
+        match steps.loop_start {
+            Some(loop_start) => Ok((ends, loop_start, steps.step - loop_start)),
+            None => Err(format!("node {} is not defined", last.0)),
+        }
+    }
+
+    // `distance_from_as_to_zs` takes the LCM of the steps to the first Z node of
+    // every ghost, which only works out when the ghosts are back on Z nodes
+    // every as many steps, and only then.
+    fn check_z_period(&self, start: &Element) -> Result<(), String> {
+        let (ends, loop_start, loop_length) = self.walk(start, |element| element.0.ends_with('Z'))?;
+        let Some(&period) = ends.first() else {
+            return Err(format!("{} never reaches a node ending with Z", start.0));
+        };
+
+        if loop_length % period != 0 {
+            return Err(format!("{} first reaches a node ending with Z after {} steps but loops every {}", start.0, period, loop_length));
+        }
+        let multiples = (1..).map(|k| k * period).take_while(|&steps| steps < loop_start + loop_length);
+        if !ends.iter().copied().eq(multiples) {
+            let steps = ends.iter().take(4).map(|steps| steps.to_string()).collect::<Vec<_>>().join(", ");
+            return Err(format!("{} is on nodes ending with Z after {}{} steps", start.0, steps, if ends.len() > 4 { ", ..." } else { "" }));
+        }
+
+        Ok(())
+    }
+}
+
+impl Lint for Network {
+    fn lint(&self) -> Vec<Check> {
+        let mut elements = self.node_by_element.keys().collect::<Vec<_>>();
+        elements.sort_by_key(|element| &element.0);
+
+        let aaa = Element("AAA".into());
+        let zzz_unreachable = match self.walk(&aaa, |element| element.0 == "ZZZ") {
+            Ok((ends, _, _)) if ends.is_empty() => Some("AAA loops without reaching ZZZ".to_string()),
+            Ok(_) => None,
+            Err(err) => Some(err),
+        };
+
+        let unperiodic = elements
+            .iter()
+            .filter(|element| element.0.ends_with('A'))
+            .find_map(|start| self.check_z_period(start).err());
+
+        vec![
+            Check::new("AAA reaches ZZZ", zzz_unreachable),
+            Check::new("ghosts are on nodes ending with Z exactly every N steps, N being the steps to the first one", unperiodic),
+        ]
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
+
+    const GHOSTS_NETWORK: &str = "\
+LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)";
 
     #[test]
     fn test_network_distance() {
@@ -154,7 +323,7 @@ LLR
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)";
-        let network = Network::from(input);
+        let network = input.parse::<Network>().unwrap();
 
         assert_eq!(network.turns, vec![Turn::Left, Turn::Left, Turn::Right]);
         assert_eq!(network.node_by_element.len(), 3);
@@ -165,39 +334,169 @@ ZZZ = (ZZZ, ZZZ)";
     }
 
     #[test]
-    fn test_network_find_z_period() {
+    fn test_network_from_invalid_str() {
         let input = "\
-LR
+LLX
 
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-        let network = Network::from(input);
+AAA = (BBB, BBB)";
+        let err = input.parse::<Network>().err();
+        assert_eq!(Some(ParseError::new(1, 3, ErrorKind::InvalidTurn('X'))), err);
+
+        let input = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA ZZZ)";
+        let err = input.parse::<Network>().err();
+        assert_eq!(Some(ParseError::new(4, 7, ErrorKind::InvalidNode("(AAA ZZZ)".into()))), err);
+
+        let err = "L\n\nAAA = (BBB, BBB)".parse::<Network>().err();
+        assert_eq!(Some(ParseError::new(3, 8, ErrorKind::UnknownNode("BBB".into()))), err);
+
+        let err = "L\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, CCC)\nZZZ = (ZZZ, ZZZ)".parse::<Network>().err();
+        assert_eq!(Some(ParseError::new(4, 13, ErrorKind::UnknownNode("CCC".into()))), err);
+    }
+
+    #[test]
+    fn test_network_steps() {
+        let network = GHOSTS_NETWORK.parse::<Network>().unwrap();
+        let from = Element("11A".into());
+        let mut steps = network.steps(&from);
+
+        let elements = steps.by_ref().map(|(step, element)| (step, element.0.as_str())).collect::<Vec<_>>();
+        assert_eq!(vec![(0, "11A"), (1, "11B"), (2, "11Z")], elements);
+        // Back on 11B before a left turn, as after the first step.
+        assert_eq!((Some(1), 3), (steps.loop_start, steps.step));
+    }
+
+    #[test]
+    fn test_network_find_z_period() {
+        let network = GHOSTS_NETWORK.parse::<Network>().unwrap();
 
-        assert_eq!(network.find_z_period(&Element("11A".into())), 2);
-        assert_eq!(network.find_z_period(&Element("22A".into())), 3);
+        assert_eq!(network.find_z_period(&Element("11A".into())), Some(2));
+        assert_eq!(network.find_z_period(&Element("22A".into())), Some(3));
+        assert_eq!(network.find_z_period(&Element("XXX".into())), None);
     }
 
     #[test]
     fn test_network_distance_from_as_to_zs() {
-        let input = "\
-LR
+        let network = GHOSTS_NETWORK.parse::<Network>().unwrap();
 
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)";
-        let network = Network::from(input);
+        assert_eq!(network.distance_from_as_to_zs(), Some(6));
+    }
+
+    #[test]
+    fn test_network_without_answers() {
+        // AAA loops on itself, and there are no ghosts.
+        let mut network = "L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)".parse::<Network>().unwrap();
+        assert_eq!(None, network.distance(Element("AAA".into()), Element("ZZZ".into())));
+        assert_eq!(Answer::Unsolved, network.part1());
+
+        let mut network = "L\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)".parse::<Network>().unwrap();
+        assert_eq!(None, network.distance_from_as_to_zs());
+        assert_eq!(Answer::Unsolved, network.part2());
+
+        // No AAA in the ghosts network, and the ghost from AAA is stuck.
+        let mut network = GHOSTS_NETWORK.parse::<Network>().unwrap();
+        assert_eq!(Answer::Unsolved, network.part1());
+        let network = "L\n\nAAA = (AAA, AAA)\n11A = (11Z, 11Z)\n11Z = (11Z, 11Z)".parse::<Network>().unwrap();
+        assert_eq!(None, network.distance_from_as_to_zs());
+    }
+
+    fn violations(input: &str) -> Vec<Option<String>> {
+        input.parse::<Network>().unwrap().lint().into_iter().map(|check| check.violation).collect()
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(
+            vec![Some("node AAA is not defined".to_string()), None],
+            violations(GHOSTS_NETWORK),
+        );
+
+        // ZZZ leading to itself, the ghost from AAA is then on it after every step.
+        assert_eq!(
+            vec![None, Some("AAA is on nodes ending with Z after 2, 3 steps".to_string())],
+            violations("RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\nCCC = (ZZZ, GGG)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)\nGGG = (GGG, GGG)\nZZZ = (ZZZ, ZZZ)"),
+        );
+        assert_eq!(
+            vec![Some("AAA loops without reaching ZZZ".to_string()), Some("AAA never reaches a node ending with Z".to_string())],
+            violations("L\n\nAAA = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)"),
+        );
+
+        // The first Z node after 2 steps, then a loop of 3 steps.
+        assert_eq!(
+            Some("11A first reaches a node ending with Z after 2 steps but loops every 3".to_string()),
+            violations("L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11C, 11C)\n11C = (11D, 11D)\n11D = (11Z, 11Z)")[1],
+        );
+    }
 
-        assert_eq!(network.distance_from_as_to_zs(), 6);
+    // A network where every ghost walks from its `A` node through `length - 1`
+    // nodes to its `Z` node and then loops back to the node after `A`, which
+    // is the structure the LCM in `distance_from_as_to_zs` relies on.
+    fn ghosts_network(turns: &[bool], lengths: &[usize]) -> String {
+        let mut network = turns.iter().map(|&left| if left { 'L' } else { 'R' }).collect::<String>();
+        network.push_str("\n\n");
+
+        for (ghost, &length) in lengths.iter().enumerate() {
+            let node = |step: usize| match step {
+                0 => format!("{}A", ghost),
+                _ if step == length => format!("{}Z", ghost),
+                _ => format!("{}N{}", ghost, step),
+            };
+
+            for step in 0..length {
+                network.push_str(&format!("{} = ({}, {})\n", node(step), node(step + 1), node(step + 1)));
+            }
+            network.push_str(&format!("{} = ({}, {})\n", node(length), node(1), node(1)));
+        }
+
+        network
+    }
+
+    // Reference implementation moving every ghost one step at a time.
+    fn simulate_ghosts(network: &Network) -> usize {
+        let mut ghosts = network.node_by_element
+            .keys()
+            .filter(|element| element.0.ends_with('A'))
+            .collect::<Vec<_>>();
+
+        for steps in 0.. {
+            if ghosts.iter().all(|element| element.0.ends_with('Z')) {
+                return steps;
+            }
+
+            for ghost in ghosts.iter_mut() {
+                let node = &network.node_by_element[*ghost];
+                *ghost = match network.turns[steps % network.turns.len()] {
+                    Turn::Left => &node.0,
+                    Turn::Right => &node.1,
+                };
+            }
+        }
+
+        unreachable!()
+    }
+
+    proptest! {
+        #[test]
+        fn test_distance_from_as_to_zs_matches_simulation(
+            turns in prop::collection::vec(any::<bool>(), 1..4),
+            lengths in prop::collection::vec(1usize..10, 1..4),
+        ) {
+            let network = ghosts_network(&turns, &lengths).parse::<Network>().unwrap();
+
+            prop_assert_eq!(Some(simulate_ghosts(&network)), network.distance_from_as_to_zs());
+        }
+
+        #[test]
+        fn test_lint_accepts_ghosts_networks(
+            turns in prop::collection::vec(any::<bool>(), 1..4),
+            lengths in prop::collection::vec(1usize..10, 1..4),
+        ) {
+            let network = ghosts_network(&turns, &lengths).parse::<Network>().unwrap();
+
+            prop_assert!(network.lint()[1].holds());
+        }
     }
 }
@@ -1,11 +0,0 @@
-fn gcd(a: usize, b: usize) -> usize {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
-}
-
-pub fn lcm(a: usize, b: usize) -> usize {
-    a * b / gcd(a, b)
-}
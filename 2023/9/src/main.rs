@@ -1,18 +1,18 @@
+use aoc_core::{input, parse};
 use day_9::oasis;
 
 fn main() {
-    let input = include_str!("../input.txt");
+    let input = input::from_args_or_exit(9);
+    let histories = parse::or_exit(9, parse::lines(&input, str::parse::<oasis::History>));
 
-    let part1 = input
-        .lines()
-        .map(|l| oasis::History::from(l))
+    let part1 = histories
+        .iter()
         .map(|h| h.predict_next_value())
         .sum::<isize>();
     println!("part 1: {}", part1);
 
-    let part2 = input
-        .lines()
-        .map(|l| oasis::History::from(l))
+    let part2 = histories
+        .iter()
         .map(|h| h.predict_previous_value())
         .sum::<isize>();
     println!("part 2: {}", part2);
@@ -1,3 +1,7 @@
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::{parse, Answer, Solution};
+
 pub struct History {
     values: Vec<isize>,
 }
@@ -46,7 +50,7 @@ impl History {
                 })
                 .collect::<Vec<_>>();
 
-            previous_value = previous_value + aggregation_coefficient * next_values[0];
+            previous_value += aggregation_coefficient * next_values[0];
             aggregation_coefficient *= -1;
         }
 
@@ -54,14 +58,69 @@ impl History {
     }
 }
 
-impl From<&str> for History {
-    fn from(s: &str) -> Self {
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {
+    Empty,
+    InvalidValue(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Empty => write!(f, "a history needs at least one value"),
+            ErrorKind::InvalidValue(s) => write!(f, "invalid value {:?}", s),
+        }
+    }
+}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+impl FromStr for History {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         let values = s
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect::<Vec<_>>();
+            .map(|v| {
+                v.parse().map_err(|_| {
+                    ParseError::new(1, parse::column(s, v), ErrorKind::InvalidValue(v.to_string()))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if values.is_empty() {
+            return Err(ParseError::new(1, 1, ErrorKind::Empty));
+        }
+
+        Ok(Self::new(values))
+    }
+}
+
+pub struct Report {
+    histories: Vec<History>,
+}
+
+impl Solution for Report {
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {
+        Ok(Self { histories: parse::lines(input, str::parse)? })
+    }
 
-        Self::new(values)
+    fn part1(&mut self) -> Answer {
+        self.histories
+            .iter()
+            .map(|h| h.predict_next_value())
+            .sum::<isize>()
+            .into()
+    }
+
+    fn part2(&mut self) -> Answer {
+        self.histories
+            .iter()
+            .map(|h| h.predict_previous_value())
+            .sum::<isize>()
+            .into()
     }
 }
 
@@ -71,32 +130,41 @@ mod tests {
 
     #[test]
     fn test_history_from_str() {
-        let history = History::from("0 3 6 9 12 15");
+        let history = "0 3 6 9 12 15".parse::<History>().unwrap();
 
         assert_eq!(history.values, vec![0, 3, 6, 9, 12, 15]);
     }
 
+    #[test]
+    fn test_history_from_invalid_str() {
+        let err = "0 3 6 9 1.2 15".parse::<History>().err();
+        assert_eq!(Some(ParseError::new(1, 9, ErrorKind::InvalidValue("1.2".into()))), err);
+
+        let err = Report::parse("0 3 6\n\n1 3 6").err();
+        assert_eq!(Some(ParseError::new(2, 1, ErrorKind::Empty)), err);
+    }
+
     #[test]
     fn test_history_predict_next_value() {
-        let history = History::from("0 3 6 9 12 15");
+        let history = "0 3 6 9 12 15".parse::<History>().unwrap();
         assert_eq!(18, history.predict_next_value());
 
-        let history = History::from("1 3 6 10 15 21");
+        let history = "1 3 6 10 15 21".parse::<History>().unwrap();
         assert_eq!(28, history.predict_next_value());
 
-        let history = History::from("10 13 16 21 30 45");
+        let history = "10 13 16 21 30 45".parse::<History>().unwrap();
         assert_eq!(68, history.predict_next_value());
     }
 
     #[test]
     fn test_history_predict_previous_value() {
-        let history = History::from("0 3 6 9 12 15");
+        let history = "0 3 6 9 12 15".parse::<History>().unwrap();
         assert_eq!(-3, history.predict_previous_value());
 
-        let history = History::from("1 3 6 10 15 21");
+        let history = "1 3 6 10 15 21".parse::<History>().unwrap();
         assert_eq!(0, history.predict_previous_value());
 
-        let history = History::from("10 13 16 21 30 45");
+        let history = "10 13 16 21 30 45".parse::<History>().unwrap();
         assert_eq!(5, history.predict_previous_value());
     }
 }
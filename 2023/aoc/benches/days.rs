@@ -0,0 +1,57 @@
+// Times parsing and both parts of every registered day, on the real input when
+// `<day>/input.txt` exists and on synthetic inputs of growing size otherwise.
+//
+// Record a baseline, then compare a later run against it:
+//
+//   cargo bench -p aoc -- --save-baseline main
+//   cargo bench -p aoc -- --baseline main
+//
+// Criterion reports every benchmark whose time changed significantly against
+// the baseline as "Performance has regressed" (or improved).
+
+use aoc::{registry, synthetic};
+use aoc_core::{input, Day};
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+// Scales at which the synthetic inputs are generated, see `synthetic::input`.
+const SCALES: [usize; 2] = [1, 10];
+
+fn bench_input(c: &mut Criterion, day: &Day, name: &str, input: &str) {
+    let mut group = c.benchmark_group(format!("day-{:02}", day.number));
+    group.sample_size(20);
+
+    group.bench_with_input(BenchmarkId::new("parse", name), input, |b, input| {
+        b.iter(|| day.parse(input).unwrap())
+    });
+
+    for part in 1..=2 {
+        // Parts may mutate the solution, so each iteration works on a fresh one.
+        group.bench_with_input(BenchmarkId::new(format!("part {}", part), name), input, |b, input| {
+            b.iter_batched(
+                || day.parse(input).unwrap(),
+                |mut solution| solution.solve(part),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    for day in registry::DAYS.iter() {
+        match input::Source::from_arg(None, day.number).read() {
+            Ok(input) => bench_input(c, day, "real", &input),
+            Err(_) => {
+                for scale in SCALES {
+                    if let Some(input) = synthetic::input(day.number, scale) {
+                        bench_input(c, day, &format!("synthetic-{}", scale), &input);
+                    }
+                }
+            }
+        }
+    }
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
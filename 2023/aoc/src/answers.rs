@@ -0,0 +1,173 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const YEAR: u16 = 2023;
+
+// Answers are only valid for the input they were computed from, so they are
+// keyed by a hash of that input as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub input_hash: u64,
+}
+
+impl Key {
+    pub fn new(day: u8, part: u8, input: &str) -> Self {
+        Self { year: YEAR, day, part, input_hash: input_hash(input) }
+    }
+}
+
+// 64 bit FNV-1a, which unlike `DefaultHasher` is stable across Rust releases.
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(PathBuf, io::Error),
+    Malformed { path: PathBuf, line: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(path, err) => write!(f, "could not access {}: {}", path.display(), err),
+            Error::Malformed { path, line } => write!(
+                f,
+                "{}:{}: expected \"<year> <day> <part> <input hash> <answer>\"",
+                path.display(),
+                line,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Answers confirmed for each part of each day, stored one per line as
+// `<year> <day> <part> <input hash> <answer>`.
+#[derive(Debug)]
+pub struct Store {
+    path: PathBuf,
+    answers: BTreeMap<Key, String>,
+}
+
+impl Store {
+    // Answers live next to the inputs they were computed from.
+    pub fn default_path(input_dir: &Path) -> PathBuf {
+        input_dir.join("answers.txt")
+    }
+
+    // A missing file is an empty store, it gets created by `save`.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(Error::Io(path.to_path_buf(), err)),
+        };
+
+        let mut answers = BTreeMap::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (key, answer) = parse_line(line).ok_or_else(|| Error::Malformed {
+                path: path.to_path_buf(),
+                line: i + 1,
+            })?;
+            answers.insert(key, answer.to_string());
+        }
+
+        Ok(Self { path: path.to_path_buf(), answers })
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&str> {
+        self.answers.get(key).map(String::as_str)
+    }
+
+    // Returns the answer previously recorded under `key`, if any.
+    pub fn insert(&mut self, key: Key, answer: String) -> Option<String> {
+        self.answers.insert(key, answer)
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let text: String = self.answers
+            .iter()
+            .map(|(key, answer)| {
+                format!("{} {} {} {:016x} {}\n", key.year, key.day, key.part, key.input_hash, answer)
+            })
+            .collect();
+
+        std::fs::write(&self.path, text).map_err(|err| Error::Io(self.path.clone(), err))
+    }
+}
+
+fn parse_line(line: &str) -> Option<(Key, &str)> {
+    let mut fields = line.splitn(5, ' ');
+
+    let key = Key {
+        year: fields.next()?.parse().ok()?,
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        input_hash: u64::from_str_radix(fields.next()?, 16).ok()?,
+    };
+    let answer = fields.next().filter(|answer| !answer.is_empty())?;
+
+    Some((key, answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(0xcbf29ce484222325, input_hash(""));
+        assert_eq!(0xaf63dc4c8601ec8c, input_hash("a"));
+        assert_ne!(input_hash("seeds: 79 14"), input_hash("seeds: 79 15"));
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let key = Key { year: 2023, day: 5, part: 2, input_hash: 0xff };
+        assert_eq!(Some((key, "46")), parse_line("2023 5 2 00000000000000ff 46"));
+        assert_eq!(Some((key, "two words")), parse_line("2023 5 2 ff two words"));
+
+        assert_eq!(None, parse_line("2023 5 2 00000000000000ff"));
+        assert_eq!(None, parse_line("2023 5 x 00000000000000ff 46"));
+        assert_eq!(None, parse_line("2023 5 2 xyz 46"));
+    }
+
+    #[test]
+    fn test_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.txt", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut store = Store::open(&path).unwrap();
+        assert_eq!(None, store.get(&Key::new(7, 2, "32T3K 765")));
+
+        assert_eq!(None, store.insert(Key::new(7, 2, "32T3K 765"), "5905".into()));
+        assert_eq!(Some("5905".into()), store.insert(Key::new(7, 2, "32T3K 765"), "5906".into()));
+        store.insert(Key::new(7, 2, "KK677 28"), "28".into());
+        store.save().unwrap();
+
+        let store = Store::open(&path).unwrap();
+        assert_eq!(Some("5906"), store.get(&Key::new(7, 2, "32T3K 765")));
+        assert_eq!(Some("28"), store.get(&Key::new(7, 2, "KK677 28")));
+        assert_eq!(None, store.get(&Key::new(7, 1, "32T3K 765")));
+
+        std::fs::write(&path, "2023 7 2 ff 5905\nnot an answer\n").unwrap();
+        match Store::open(&path) {
+            Err(Error::Malformed { line, .. }) => assert_eq!(2, line),
+            _ => panic!("expected a malformed answers file error"),
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
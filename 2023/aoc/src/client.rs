@@ -0,0 +1,201 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_core::input;
+
+use crate::answers::YEAR;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// Environment variable overriding the server the client talks to, mostly
+// useful to point it at a local stub.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+// Environment variable holding the value of the adventofcode.com session cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = concat!("aoc-2023/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum Error {
+    MissingSession(PathBuf),
+    Io(PathBuf, io::Error),
+    Status { url: String, code: u16, body: String },
+    Transport(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingSession(path) => write!(
+                f,
+                "no session token, set {} or save the session cookie in {}",
+                SESSION_VAR,
+                path.display(),
+            ),
+            Error::Io(path, err) => write!(f, "could not access {}: {}", path.display(), err),
+            Error::Status { url, code, body } => write!(f, "{} returned {}: {}", url, code, body.trim()),
+            Error::Transport(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn default_base_url() -> String {
+    std::env::var(BASE_URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+// `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`.
+pub fn session_path() -> PathBuf {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(std::env::var_os("HOME").unwrap_or_default()).join(".config"),
+    };
+
+    config_dir.join("aoc").join("session")
+}
+
+// The session token from the environment, or else from the config file.
+pub fn session() -> Result<String, Error> {
+    if let Ok(session) = std::env::var(SESSION_VAR) {
+        if !session.trim().is_empty() {
+            return Ok(session.trim().to_string());
+        }
+    }
+
+    let path = session_path();
+    match std::fs::read_to_string(&path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        Ok(_) => Err(Error::MissingSession(path)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Err(Error::MissingSession(path)),
+        Err(err) => Err(Error::Io(path, err)),
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}", self.base_url, YEAR, day)
+    }
+
+    fn get(&self, url: &str) -> Result<String, Error> {
+        let request = self.agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session));
+
+        read_response(url, request.call())
+    }
+
+    pub fn input(&self, day: u8) -> Result<String, Error> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    // Posts `answer` for `part` of `day` and returns the page the server replied with.
+    pub fn answer(&self, day: u8, part: u8, answer: &str) -> Result<String, Error> {
+        let url = format!("{}/answer", self.day_url(day));
+        let request = self.agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session));
+
+        read_response(&url, request.send_form(&[("level", &part.to_string()), ("answer", answer)]))
+    }
+}
+
+fn read_response(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, Error> {
+    match response {
+        Ok(response) => response.into_string().map_err(|err| Error::Transport(err.to_string())),
+        Err(ureq::Error::Status(code, response)) => Err(Error::Status {
+            url: url.to_string(),
+            code,
+            body: response.into_string().unwrap_or_default(),
+        }),
+        Err(err) => Err(Error::Transport(err.to_string())),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+// Downloads the input of `day` into `<dir>/<day>/input.txt`, unless it has
+// already been downloaded there: inputs never change, and the server asks
+// for them not to be requested over and over.
+pub fn fetch_input(client: &Client, dir: &Path, day: u8) -> Result<Fetched, Error> {
+    let path = input::lookup_path(dir, day);
+    if path.exists() {
+        return Ok(Fetched::Cached(path));
+    }
+
+    let input = client.input(day)?;
+
+    let day_dir = path.parent().unwrap_or(dir);
+    std::fs::create_dir_all(day_dir).map_err(|err| Error::Io(day_dir.to_path_buf(), err))?;
+    std::fs::write(&path, input).map_err(|err| Error::Io(path.clone(), err))?;
+
+    Ok(Fetched::Downloaded(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::stub_server;
+
+    #[test]
+    fn test_client_input() {
+        let (base_url, server) = stub_server(vec![(200, "0 3 6 9 12 15\n")]);
+
+        let client = Client::new(&format!("{}/", base_url), "53551");
+        assert_eq!("0 3 6 9 12 15\n", client.input(9).unwrap());
+
+        assert_eq!(vec!["GET /2023/day/9/input session=53551"], server.join().unwrap());
+    }
+
+    #[test]
+    fn test_client_input_error_status() {
+        let (base_url, server) = stub_server(vec![(400, "Please log in to get your puzzle input.")]);
+
+        match Client::new(&base_url, "expired").input(9) {
+            Err(Error::Status { code, body, .. }) => {
+                assert_eq!(400, code);
+                assert_eq!("Please log in to get your puzzle input.", body);
+            }
+            _ => panic!("expected an error status"),
+        }
+
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_fetch_input() {
+        let dir = std::env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let (base_url, server) = stub_server(vec![(200, "Time: 7\nDistance: 9\n")]);
+        let client = Client::new(&base_url, "53551");
+
+        let path = input::lookup_path(&dir, 6);
+        assert_eq!(Fetched::Downloaded(path.clone()), fetch_input(&client, &dir, 6).unwrap());
+        assert_eq!("Time: 7\nDistance: 9\n", std::fs::read_to_string(&path).unwrap());
+        server.join().unwrap();
+
+        // The stub is gone, so this would fail if it tried downloading again.
+        assert_eq!(Fetched::Cached(path), fetch_input(&client, &dir, 6).unwrap());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,145 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Example inputs live in `<dir>/<day>/<name>.txt`, next to the answers
+// expected for them in `<name>.expected`, one `part <part>: <answer>` per line.
+pub fn default_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("fixtures")
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Fixture {
+    pub day: u8,
+    pub name: String,
+    pub input: String,
+    pub expected: Vec<(u8, String)>,
+}
+
+pub fn format_expected(expected: &[(u8, String)]) -> String {
+    expected
+        .iter()
+        .map(|(part, answer)| format!("part {}: {}\n", part, answer))
+        .collect()
+}
+
+pub fn parse_expected(text: &str) -> Option<Vec<(u8, String)>> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (part, answer) = line.strip_prefix("part ")?.split_once(": ")?;
+            Some((part.parse().ok()?, answer.to_string()))
+        })
+        .collect()
+}
+
+impl Fixture {
+    fn paths(&self, dir: &Path) -> (PathBuf, PathBuf) {
+        let day_dir = dir.join(self.day.to_string());
+        (day_dir.join(format!("{}.txt", self.name)), day_dir.join(format!("{}.expected", self.name)))
+    }
+
+    // Writes the fixture unless one with the same name exists, so fixtures
+    // corrected by hand are left alone. Returns whether it was written.
+    pub fn write(&self, dir: &Path) -> io::Result<bool> {
+        let (input_path, expected_path) = self.paths(dir);
+        if input_path.exists() || expected_path.exists() {
+            return Ok(false);
+        }
+
+        std::fs::create_dir_all(dir.join(self.day.to_string()))?;
+        std::fs::write(input_path, &self.input)?;
+        std::fs::write(expected_path, format_expected(&self.expected))?;
+
+        Ok(true)
+    }
+}
+
+fn invalid(path: &Path, message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), message))
+}
+
+// Every fixture under `dir`, ordered by day and name.
+pub fn discover(dir: &Path) -> io::Result<Vec<Fixture>> {
+    let mut fixtures = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let day_dir = entry?.path();
+        let Some(day) = day_dir.file_name().and_then(|name| name.to_str()?.parse::<u8>().ok()) else {
+            continue;
+        };
+
+        for entry in std::fs::read_dir(&day_dir)? {
+            let input_path = entry?.path();
+            if input_path.extension().is_none_or(|extension| extension != "txt") {
+                continue;
+            }
+            let name = input_path
+                .file_stem()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| invalid(&input_path, "fixture names must be valid UTF-8"))?
+                .to_string();
+
+            let expected_path = input_path.with_extension("expected");
+            let expected = match std::fs::read_to_string(&expected_path) {
+                Ok(expected) => expected,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    return Err(invalid(&expected_path, "missing, every fixture needs its expected answers"));
+                }
+                Err(err) => return Err(err),
+            };
+            let expected = parse_expected(&expected)
+                .ok_or_else(|| invalid(&expected_path, "expected one \"part <part>: <answer>\" per line"))?;
+
+            fixtures.push(Fixture { day, name, input: std::fs::read_to_string(&input_path)?, expected });
+        }
+    }
+
+    fixtures.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        let expected = vec![(1, "6440".to_string()), (2, "5905".to_string())];
+
+        assert_eq!("part 1: 6440\npart 2: 5905\n", format_expected(&expected));
+        assert_eq!(Some(expected), parse_expected("part 1: 6440\n\npart 2: 5905\n"));
+        assert_eq!(Some(vec![]), parse_expected(""));
+        assert_eq!(None, parse_expected("part one: 6440\n"));
+        assert_eq!(None, parse_expected("6440\n"));
+    }
+
+    #[test]
+    fn test_fixture_write() {
+        let dir = std::env::temp_dir().join(format!("aoc-fixtures-{}", std::process::id()));
+        let fixture = Fixture {
+            day: 9,
+            name: "example-1".into(),
+            input: "0 3 6 9 12 15\n".into(),
+            expected: vec![(1, "18".into())],
+        };
+
+        assert!(fixture.write(&dir).unwrap());
+        assert_eq!("0 3 6 9 12 15\n", std::fs::read_to_string(dir.join("9").join("example-1.txt")).unwrap());
+        assert_eq!("part 1: 18\n", std::fs::read_to_string(dir.join("9").join("example-1.expected")).unwrap());
+
+        let changed = Fixture { input: "1 3 6 10 15 21\n".into(), ..fixture };
+        assert!(!changed.write(&dir).unwrap());
+        assert_eq!("0 3 6 9 12 15\n", std::fs::read_to_string(dir.join("9").join("example-1.txt")).unwrap());
+
+        let Fixture { input, .. } = changed;
+        assert_eq!(
+            vec![Fixture { day: 9, name: "example-1".into(), input: "0 3 6 9 12 15\n".into(), expected: vec![(1, "18".into())] }],
+            discover(&dir).unwrap(),
+        );
+
+        std::fs::write(dir.join("9").join("example-2.txt"), input).unwrap();
+        assert_eq!(io::ErrorKind::InvalidData, discover(&dir).unwrap_err().kind());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
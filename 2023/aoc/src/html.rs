@@ -0,0 +1,36 @@
+// Text out of the bits of HTML pages the server sends back.
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+// The text of a bit of a page, without its markup.
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    unescape(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_tags() {
+        assert_eq!("Cosmic Expansion", strip_tags("<em>Cosmic</em> Expansion"));
+        assert_eq!("a < b && \"c\"", strip_tags("<code>a &lt; b &amp;&amp; &quot;c&quot;</code>"));
+    }
+}
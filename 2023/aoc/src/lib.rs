@@ -0,0 +1,13 @@
+pub mod answers;
+pub mod client;
+pub mod fixtures;
+pub mod html;
+pub mod puzzle;
+pub mod profile;
+pub mod registry;
+pub mod report;
+pub mod scaffold;
+#[cfg(test)]
+mod stub;
+pub mod submit;
+pub mod synthetic;
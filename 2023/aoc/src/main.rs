@@ -0,0 +1,772 @@
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use aoc::answers::{self, Key, Store};
+use aoc::puzzle;
+use aoc::client::{self, Client, Fetched};
+use aoc::fixtures::{self, Fixture};
+use aoc::submit::{self, Log, Outcome};
+use aoc::profile::{self, CountingAllocator, Meter};
+use aoc::registry;
+use aoc::report::Record;
+use aoc::scaffold::{self, Scaffold};
+use aoc::synthetic;
+use aoc_core::render::{self, Frame};
+use aoc_core::{input, Answer, Day, Solution};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2023 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or every day with --all
+    Run(RunArgs),
+    /// Solve every day again and compare against the recorded answers
+    Verify(VerifyArgs),
+    /// Download the puzzle input of a day, unless it is already there
+    Fetch(FetchArgs),
+    /// Solve a part of a day and submit its answer
+    Submit(SubmitArgs),
+    /// Create the crate of a new day and register it with the workspace and the runner
+    New(NewArgs),
+    /// Save the examples of a puzzle page and their answers as test fixtures
+    Examples(ExamplesArgs),
+    /// Print a random input for a day, the same one for the same seed
+    Gen(GenArgs),
+    /// Draw the solution of a day in the terminal
+    Show(ShowArgs),
+    /// Check the assumptions the solutions make about their inputs
+    Lint(LintArgs),
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Only solve this part (1 or 2), both parts are solved by default
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    #[arg(long)]
+    all: bool,
+
+    /// Read the input from this file, or from stdin when it is -
+    #[arg(long, conflicts_with_all = ["all", "input_dir"])]
+    input: Option<String>,
+
+    /// Look up inputs as <DIR>/<day>/input.txt [default: $AOC_INPUT_DIR or the current directory]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Record the answers `submit` had accepted, for `verify` to check against later
+    #[arg(long)]
+    record: bool,
+
+    /// With --record, also record answers that were never accepted
+    #[arg(long, requires = "record")]
+    force: bool,
+
+    /// Answers file [default: answers.txt in the input directory]
+    #[arg(long, value_name = "FILE")]
+    answers: Option<PathBuf>,
+
+    /// Log of the submissions made so far [default: submissions.txt in the input directory]
+    #[arg(long, value_name = "FILE")]
+    log: Option<PathBuf>,
+
+    /// Print the answers as text, or as one JSON object per part with timings
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Print a table of the time taken and the heap used by every day, counting allocations slows them down a little
+    #[arg(long, conflicts_with = "format")]
+    profile: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+struct VerifyArgs {
+    /// Look up inputs as <DIR>/<day>/input.txt [default: $AOC_INPUT_DIR or the current directory]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Answers file [default: answers.txt in the input directory]
+    #[arg(long, value_name = "FILE")]
+    answers: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct FetchArgs {
+    #[arg(long)]
+    day: u8,
+
+    /// Save inputs as <DIR>/<day>/input.txt [default: $AOC_INPUT_DIR or the current directory]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Server to download from [default: $AOC_BASE_URL or https://adventofcode.com]
+    #[arg(long, value_name = "URL")]
+    base_url: Option<String>,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    #[arg(long)]
+    day: u8,
+
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+
+    /// Look up inputs as <DIR>/<day>/input.txt [default: $AOC_INPUT_DIR or the current directory]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Server to submit to [default: $AOC_BASE_URL or https://adventofcode.com]
+    #[arg(long, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Answers file in which correct answers get recorded [default: answers.txt in the input directory]
+    #[arg(long, value_name = "FILE")]
+    answers: Option<PathBuf>,
+
+    /// Log of the submissions made so far [default: submissions.txt in the input directory]
+    #[arg(long, value_name = "FILE")]
+    log: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct NewArgs {
+    #[arg(long)]
+    day: u8,
+
+    /// Puzzle page saved from the website, to take the title and the example from
+    #[arg(long, value_name = "FILE")]
+    puzzle: Option<PathBuf>,
+
+    /// Puzzle title [default: the one in the puzzle page, or "Day <day>"]
+    #[arg(long)]
+    title: Option<String>,
+
+    /// Name of the solution module [default: derived from the title]
+    #[arg(long)]
+    module: Option<String>,
+
+    /// Workspace to add the crate to [default: the current directory]
+    #[arg(long, value_name = "DIR")]
+    workspace: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ExamplesArgs {
+    #[arg(long)]
+    day: u8,
+
+    /// Puzzle page saved from the website
+    #[arg(long, value_name = "FILE")]
+    puzzle: PathBuf,
+
+    /// Save fixtures as <DIR>/<day>/example-<n>.txt [default: the runner's tests/fixtures]
+    #[arg(long, value_name = "DIR")]
+    fixtures: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct GenArgs {
+    #[arg(long)]
+    day: u8,
+
+    /// About how many lines to generate, or rows for the grids
+    #[arg(long, default_value_t = 100)]
+    size: usize,
+
+    /// Seed of the input, printed on stderr [default: a random one]
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+#[derive(Args)]
+struct ShowArgs {
+    #[arg(long)]
+    day: u8,
+
+    /// Read the input from this file, or from stdin when it is -
+    #[arg(long, conflicts_with = "input_dir")]
+    input: Option<String>,
+
+    /// Look up the input as <DIR>/<day>/input.txt [default: $AOC_INPUT_DIR or the current directory]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Play every step of the drawing instead of only showing the result
+    #[arg(long)]
+    animate: bool,
+
+    /// Milliseconds between the frames of the animation
+    #[arg(long, default_value_t = 50, requires = "animate")]
+    delay: u64,
+
+    /// Draw without colors, which is the default when $NO_COLOR is set
+    #[arg(long)]
+    no_color: bool,
+}
+
+#[derive(Args)]
+struct LintArgs {
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Check every day making assumptions whose input is there
+    #[arg(long)]
+    all: bool,
+
+    /// Read the input from this file, or from stdin when it is -
+    #[arg(long, conflicts_with_all = ["all", "input_dir"])]
+    input: Option<String>,
+
+    /// Look up inputs as <DIR>/<day>/input.txt [default: $AOC_INPUT_DIR or the current directory]
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+}
+
+// Without a path, the answers file is looked up in the input directory.
+fn open_answers(path: Option<&Path>, input_dir: Option<&Path>) -> Result<Store, answers::Error> {
+    match path {
+        Some(path) => Store::open(path),
+        None => Store::open(&Store::default_path(&input_dir.map_or_else(input::default_dir, Path::to_path_buf))),
+    }
+}
+
+// Without a path, the submissions log is looked up in the input directory.
+fn open_log(path: Option<&Path>, input_dir: Option<&Path>) -> Result<Log, submit::Error> {
+    match path {
+        Some(path) => Log::open(path),
+        None => Log::open(&Log::default_path(&input_dir.map_or_else(input::default_dir, Path::to_path_buf))),
+    }
+}
+
+// Parses the input of `day`, reporting a failure on stderr.
+fn parse(day: &Day, input: &str) -> Option<Box<dyn Solution>> {
+    match day.parse(input) {
+        Ok(solution) => Some(solution),
+        Err(err) => {
+            eprintln!("Day {}: {}", day.number, err);
+            None
+        }
+    }
+}
+
+impl RunArgs {
+    fn input_source(&self, day: u8) -> input::Source {
+        match &self.input_dir {
+            Some(dir) => input::Source::Lookup { dir: dir.clone(), day },
+            None => input::Source::from_arg(self.input.as_deref(), day),
+        }
+    }
+}
+
+fn run(args: &RunArgs) -> ExitCode {
+    let days: Vec<&Day> = match args.day {
+        Some(number) => match registry::find(number) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("Day {} is not implemented", number);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => registry::DAYS.iter().collect(),
+    };
+    let parts: Vec<u8> = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let mut store = None;
+    if args.record {
+        match open_answers(args.answers.as_deref(), args.input_dir.as_deref()) {
+            Ok(answers) => store = Some(answers),
+            Err(err) => {
+                eprintln!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    // Answers are only confirmed once the server accepted them, unless forced.
+    let mut log = None;
+    if args.record && !args.force {
+        match open_log(args.log.as_deref(), args.input_dir.as_deref()) {
+            Ok(submissions) => log = Some(submissions),
+            Err(err) => {
+                eprintln!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let mut exit_code = ExitCode::SUCCESS;
+    // Without --profile nothing is metered, so that plain runs are not skewed.
+    let mut profile_rows = args.profile.then(Vec::new);
+    if args.profile {
+        CountingAllocator::enable();
+    }
+
+    for day in days {
+        let input = match args.input_source(day.number).read() {
+            Ok(input) => input,
+            Err(err) => {
+                match args.format {
+                    Format::Text => eprintln!("Day {}: {}", day.number, err),
+                    Format::Json => parts.iter().for_each(|&part| println!("{}", Record::input_error(day.number, part, &err).to_json())),
+                }
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let meter = args.profile.then(Meter::start);
+        let start = Instant::now();
+        let parsed = day.parse(&input);
+        let parse_time = start.elapsed();
+        let mut solution = match parsed {
+            Ok(solution) => solution,
+            Err(err) => {
+                match args.format {
+                    Format::Text => eprintln!("Day {}: {}", day.number, err),
+                    Format::Json => parts.iter().for_each(|&part| {
+                        println!("{}", Record::parse_error(day.number, part, &input, parse_time, err.as_ref()).to_json())
+                    }),
+                }
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        let mut part_times = Vec::new();
+        for &part in &parts {
+            let start = Instant::now();
+            let answer = solution.solve(part);
+            let solve_time = start.elapsed();
+            if args.profile {
+                part_times.push((part, solve_time));
+            }
+
+            match args.format {
+                Format::Text => println!("Day {} part {}: {}", day.number, part, answer),
+                Format::Json => println!("{}", Record::solved(day.number, part, &input, &answer, parse_time, solve_time).to_json()),
+            }
+
+            if let (Some(store), Answer::Number(_) | Answer::Text(_)) = (&mut store, &answer) {
+                let answer = answer.to_string();
+                match log.as_ref().map(|log| log.accepted(day.number, part)) {
+                    Some(None) => {
+                        eprintln!("Day {} part {}: not recording {}, it was never accepted, --force records it anyway", day.number, part, answer);
+                        continue;
+                    }
+                    Some(Some(accepted)) if accepted != answer => {
+                        eprintln!("Day {} part {}: not recording {}, {} was accepted", day.number, part, answer, accepted);
+                        continue;
+                    }
+                    _ => {}
+                }
+                match store.insert(Key::new(day.number, part, &input), answer.clone()) {
+                    Some(previous) if previous != answer => {
+                        eprintln!("Day {} part {}: replaced recorded answer {}", day.number, part, previous);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if let (Some(profile_rows), Some(meter)) = (&mut profile_rows, meter) {
+            let usage = meter.usage();
+            drop(solution);
+            profile_rows.push(profile::Row { day: day.number, parse: parse_time, parts: part_times, usage });
+        }
+    }
+
+    if let Some(profile_rows) = profile_rows {
+        print!("\n{}", profile::table(&profile_rows));
+    }
+
+    if let Some(store) = store {
+        if let Err(err) = store.save() {
+            eprintln!("{}", err);
+            exit_code = ExitCode::FAILURE;
+        }
+    }
+
+    exit_code
+}
+
+// Days without an input or without recorded answers are skipped, any answer
+// that differs from the recorded one fails the verification.
+fn verify(args: &VerifyArgs) -> ExitCode {
+    let store = match open_answers(args.answers.as_deref(), args.input_dir.as_deref()) {
+        Ok(store) => store,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let input_dir = args.input_dir.clone().unwrap_or_else(input::default_dir);
+
+    let mut verified = 0;
+    let mut failures = 0;
+
+    for day in registry::DAYS.iter() {
+        let input = match (input::Source::Lookup { dir: input_dir.clone(), day: day.number }).read() {
+            Ok(input) => input,
+            Err(input::Error::NotFound(_)) => continue,
+            Err(err) => {
+                eprintln!("Day {}: {}", day.number, err);
+                failures += 1;
+                continue;
+            }
+        };
+
+        let recorded: Vec<(u8, &str)> = [1, 2]
+            .into_iter()
+            .filter_map(|part| Some((part, store.get(&Key::new(day.number, part, &input))?)))
+            .collect();
+        if recorded.is_empty() {
+            continue;
+        }
+
+        let Some(mut solution) = parse(day, &input) else {
+            failures += 1;
+            continue;
+        };
+        for (part, expected) in recorded {
+            let answer = solution.solve(part).to_string();
+            if answer == expected {
+                println!("Day {} part {}: ok", day.number, part);
+                verified += 1;
+            } else {
+                eprintln!("Day {} part {}: MISMATCH, expected {} but got {}", day.number, part, expected, answer);
+                failures += 1;
+            }
+        }
+    }
+
+    println!("{} answers verified, {} failures", verified, failures);
+
+    if failures > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn fetch(args: &FetchArgs) -> ExitCode {
+    let session = match client::session() {
+        Ok(session) => session,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let base_url = args.base_url.clone().unwrap_or_else(client::default_base_url);
+    let input_dir = args.input_dir.clone().unwrap_or_else(input::default_dir);
+
+    match client::fetch_input(&Client::new(&base_url, &session), &input_dir, args.day) {
+        Ok(Fetched::Downloaded(path)) => {
+            println!("Day {}: saved input to {}", args.day, path.display());
+            ExitCode::SUCCESS
+        }
+        Ok(Fetched::Cached(path)) => {
+            println!("Day {}: input already saved in {}, not downloading it again", args.day, path.display());
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Day {}: {}", args.day, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn submit(args: &SubmitArgs) -> ExitCode {
+    let Some(day) = registry::find(args.day) else {
+        eprintln!("Day {} is not implemented", args.day);
+        return ExitCode::FAILURE;
+    };
+    let input_dir = args.input_dir.clone().unwrap_or_else(input::default_dir);
+    let input = match (input::Source::Lookup { dir: input_dir.clone(), day: day.number }).read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {}: {}", day.number, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(mut solution) = parse(day, &input) else {
+        return ExitCode::FAILURE;
+    };
+
+    let answer = solution.solve(args.part);
+    if answer == Answer::Unsolved {
+        eprintln!("Day {} part {} is unsolved", day.number, args.part);
+        return ExitCode::FAILURE;
+    }
+    let answer = answer.to_string();
+
+    let session = match client::session() {
+        Ok(session) => session,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let base_url = args.base_url.clone().unwrap_or_else(client::default_base_url);
+
+    let outcome = open_log(args.log.as_deref(), Some(&input_dir))
+        .and_then(|mut log| submit::submit(&Client::new(&base_url, &session), &mut log, day.number, args.part, &answer));
+    let outcome = match outcome {
+        Ok(outcome) => outcome,
+        Err(err) => {
+            eprintln!("Day {} part {}: {}", day.number, args.part, err);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Day {} part {}: {} is {}", day.number, args.part, answer, outcome);
+
+    if outcome != Outcome::Correct {
+        return ExitCode::FAILURE;
+    }
+
+    let recorded = open_answers(args.answers.as_deref(), args.input_dir.as_deref()).and_then(|mut store| {
+        store.insert(Key::new(day.number, args.part, &input), answer);
+        store.save()
+    });
+    if let Err(err) = recorded {
+        eprintln!("{}", err);
+        return ExitCode::FAILURE;
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn new(args: &NewArgs) -> ExitCode {
+    let page = match &args.puzzle {
+        Some(path) => match std::fs::read_to_string(path) {
+            Ok(page) => Some(page),
+            Err(err) => {
+                eprintln!("could not read {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    let title = args.title.clone()
+        .or_else(|| page.as_deref().and_then(puzzle::title))
+        .unwrap_or_else(|| format!("Day {}", args.day));
+    let scaffold = Scaffold {
+        day: args.day,
+        module: args.module.clone().unwrap_or_else(|| scaffold::module_name(&title)),
+        title,
+        example: page.as_deref().and_then(|page| puzzle::examples(page).into_iter().next()),
+    };
+
+    let workspace = args.workspace.clone().unwrap_or_else(|| PathBuf::from("."));
+    match scaffold.create(&workspace) {
+        Ok(files) => {
+            for file in files {
+                println!("{}", file.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Day {}: {}", args.day, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn examples(args: &ExamplesArgs) -> ExitCode {
+    let page = match std::fs::read_to_string(&args.puzzle) {
+        Ok(page) => page,
+        Err(err) => {
+            eprintln!("could not read {}: {}", args.puzzle.display(), err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let dir = args.fixtures.clone().unwrap_or_else(fixtures::default_dir);
+
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for (i, example) in puzzle::examples_with_answers(&page).into_iter().enumerate() {
+        let name = format!("example-{}", i + 1);
+        if example.answers.is_empty() {
+            println!("Day {} {}: no answer found, skipped", args.day, name);
+            continue;
+        }
+
+        let fixture = Fixture { day: args.day, name, input: example.input, expected: example.answers };
+        match fixture.write(&dir) {
+            Ok(true) => {
+                let expected = fixture.expected.iter().map(|(part, answer)| format!("part {}: {}", part, answer));
+                println!("Day {} {}: saved, expecting {}", args.day, fixture.name, expected.collect::<Vec<_>>().join(", "));
+            }
+            Ok(false) => println!("Day {} {}: already saved, left alone", args.day, fixture.name),
+            Err(err) => {
+                eprintln!("Day {} {}: {}", args.day, fixture.name, err);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+fn gen(args: &GenArgs) -> ExitCode {
+    let seed = args.seed.unwrap_or_else(|| {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        now.as_secs() ^ u64::from(now.subsec_nanos())
+    });
+
+    match synthetic::random(args.day, args.size, seed) {
+        Some(input) => {
+            eprintln!("Day {}: size {}, seed {}", args.day, args.size, seed);
+            print!("{}", input);
+            ExitCode::SUCCESS
+        }
+        None => {
+            eprintln!("Day {} has no input generator", args.day);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn show(args: &ShowArgs) -> ExitCode {
+    let Some(day) = registry::find(args.day) else {
+        eprintln!("Day {} is not implemented", args.day);
+        return ExitCode::FAILURE;
+    };
+    let source = match &args.input_dir {
+        Some(dir) => input::Source::Lookup { dir: dir.clone(), day: day.number },
+        None => input::Source::from_arg(args.input.as_deref(), day.number),
+    };
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Day {}: {}", day.number, err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let color = !args.no_color && std::env::var_os("NO_COLOR").is_none();
+    let draw = |frame: &Frame| if color { frame.to_ansi() } else { frame.to_plain() };
+
+    let mut last = None;
+    let rendered = day.render(&input, &mut |frame| {
+        if args.animate {
+            print!("{}{}", render::CLEAR_SCREEN, draw(frame));
+            std::thread::sleep(std::time::Duration::from_millis(args.delay));
+        } else {
+            last = Some(draw(frame));
+        }
+    });
+
+    match rendered {
+        Some(Ok(())) => {
+            if let Some(last) = last {
+                print!("{}", last);
+            }
+            ExitCode::SUCCESS
+        }
+        Some(Err(err)) => {
+            eprintln!("Day {}: {}", day.number, err);
+            ExitCode::FAILURE
+        }
+        None => {
+            eprintln!("Day {} cannot be drawn", day.number);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// Exits with a failure when an assumption does not hold, or when a day given
+// explicitly cannot be checked.
+fn lint(args: &LintArgs) -> ExitCode {
+    let days: Vec<&Day> = match args.day {
+        Some(number) => match registry::find(number) {
+            Some(day) => vec![day],
+            None => {
+                eprintln!("Day {} is not implemented", number);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => registry::DAYS.iter().collect(),
+    };
+
+    let mut exit_code = ExitCode::SUCCESS;
+
+    for day in days {
+        let source = match (&args.input_dir, args.all) {
+            (Some(dir), _) => input::Source::Lookup { dir: dir.clone(), day: day.number },
+            (None, true) => input::Source::Lookup { dir: input::default_dir(), day: day.number },
+            (None, false) => input::Source::from_arg(args.input.as_deref(), day.number),
+        };
+        let input = match source.read() {
+            Ok(input) => input,
+            Err(input::Error::NotFound(_)) if args.all => continue,
+            Err(err) => {
+                eprintln!("Day {}: {}", day.number, err);
+                exit_code = ExitCode::FAILURE;
+                continue;
+            }
+        };
+
+        match day.lint(&input) {
+            Some(Ok(checks)) => {
+                println!("Day {}: {}", day.number, day.title);
+                for check in checks {
+                    println!("  {}", check);
+                    if !check.holds() {
+                        exit_code = ExitCode::FAILURE;
+                    }
+                }
+            }
+            Some(Err(err)) => {
+                eprintln!("Day {}: {}", day.number, err);
+                exit_code = ExitCode::FAILURE;
+            }
+            None if args.all => {}
+            None => {
+                eprintln!("Day {} makes no assumptions to check", day.number);
+                exit_code = ExitCode::FAILURE;
+            }
+        }
+    }
+
+    exit_code
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match &cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::Fetch(args) => fetch(args),
+        Command::Submit(args) => submit(args),
+        Command::New(args) => new(args),
+        Command::Examples(args) => examples(args),
+        Command::Gen(args) => gen(args),
+        Command::Show(args) => show(args),
+        Command::Lint(args) => lint(args),
+    }
+}
@@ -0,0 +1,201 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static COUNTING: AtomicBool = AtomicBool::new(false);
+
+// The system allocator, keeping count of the bytes in use, of their peak and
+// of the number of allocations. It only counts once installed in the binary
+// with `#[global_allocator]` and switched on with `enable`, until then every
+// allocation only pays for checking whether to count it.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    // Bytes allocated before are not counted, and neither is freeing them.
+    pub fn enable() {
+        COUNTING.store(true, Ordering::Relaxed);
+    }
+
+    fn allocated(&self, size: usize) {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return;
+        }
+        let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(allocated, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn deallocated(&self, size: usize) {
+        if !COUNTING.load(Ordering::Relaxed) {
+            return;
+        }
+        // Saturating, as the bytes may have been allocated before counting.
+        let _ = ALLOCATED.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |allocated| Some(allocated.saturating_sub(size)));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        self.deallocated(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            self.deallocated(layout.size());
+            self.allocated(new_size);
+        }
+        new_ptr
+    }
+}
+
+// Allocations made since a meter started.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Usage {
+    // Most bytes in use at once, on top of those in use at the start.
+    pub peak_bytes: usize,
+    pub allocations: usize,
+}
+
+// Counts the allocations from its start, meant to be used one at a time.
+pub struct Meter {
+    baseline: usize,
+}
+
+impl Meter {
+    pub fn start() -> Self {
+        let baseline = ALLOCATED.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        ALLOCATIONS.store(0, Ordering::Relaxed);
+        Self { baseline }
+    }
+
+    pub fn usage(&self) -> Usage {
+        Usage {
+            peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(self.baseline),
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub struct Row {
+    pub day: u8,
+    pub parse: Duration,
+    pub parts: Vec<(u8, Duration)>,
+    pub usage: Usage,
+}
+
+fn format_bytes(bytes: usize) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", value, units[unit]),
+    }
+}
+
+pub fn table(rows: &[Row]) -> String {
+    let mut lines = vec![format!(
+        "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}  {:>12}",
+        "Day", "Parse", "Part 1", "Part 2", "Peak heap", "Allocations"
+    )];
+
+    for row in rows {
+        let part = |part: u8| {
+            row.parts
+                .iter()
+                .find(|(p, _)| *p == part)
+                .map_or("-".to_string(), |(_, time)| format!("{:.1?}", time))
+        };
+        lines.push(format!(
+            "{:>3}  {:>10}  {:>10}  {:>10}  {:>10}  {:>12}",
+            row.day,
+            format!("{:.1?}", row.parse),
+            part(1),
+            part(2),
+            format_bytes(row.usage.peak_bytes),
+            row.usage.allocations,
+        ));
+    }
+
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_meter() {
+        // Other tests allocate concurrently, so only lower bounds hold.
+        CountingAllocator::enable();
+        let meter = Meter::start();
+        let buffer = vec![0u8; 1 << 20];
+        let grown = (0..1000).map(|i| i.to_string()).collect::<Vec<_>>();
+        let usage = meter.usage();
+        drop((buffer, grown));
+
+        assert!(usage.peak_bytes >= 1 << 20);
+        assert!(usage.allocations >= 1001);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!("0 B", format_bytes(0));
+        assert_eq!("1023 B", format_bytes(1023));
+        assert_eq!("1.5 KiB", format_bytes(1536));
+        assert_eq!("3.0 MiB", format_bytes(3 << 20));
+        assert_eq!("2048.0 GiB", format_bytes(2 << 40));
+    }
+
+    #[test]
+    fn test_table() {
+        let rows = [
+            Row {
+                day: 4,
+                parse: Duration::from_micros(250),
+                parts: vec![(1, Duration::from_nanos(900)), (2, Duration::from_millis(12))],
+                usage: Usage { peak_bytes: 5 << 20, allocations: 1234567 },
+            },
+            Row { day: 10, parse: Duration::from_secs(1), parts: vec![(2, Duration::from_secs(2))], usage: Usage { peak_bytes: 10, allocations: 1 } },
+        ];
+
+        assert_eq!(
+            "\
+Day       Parse      Part 1      Part 2   Peak heap   Allocations
+  4     250.0µs     900.0ns      12.0ms     5.0 MiB       1234567
+ 10        1.0s           -        2.0s        10 B             1
+",
+            table(&rows),
+        );
+    }
+}
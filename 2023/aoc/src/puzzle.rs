@@ -0,0 +1,194 @@
+// Bits of a puzzle description page saved from adventofcode.com.
+
+use crate::html::strip_tags;
+
+// The title in `<h2>--- Day 11: Cosmic Expansion ---</h2>`.
+pub fn title(html: &str) -> Option<String> {
+    let start = html.find("<h2>")? + "<h2>".len();
+    let end = start + html[start..].find("</h2>")?;
+    let heading = strip_tags(&html[start..end]);
+
+    let title = heading.trim().trim_matches('-').trim();
+    let (_, title) = title.split_once(": ")?;
+
+    Some(title.to_string())
+}
+
+// The contents of every `<pre><code>` block, in order of appearance.
+pub fn examples(html: &str) -> Vec<String> {
+    let mut examples = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<pre><code>") {
+        let block = &rest[start + "<pre><code>".len()..];
+        let Some(end) = block.find("</code></pre>") else {
+            break;
+        };
+
+        examples.push(strip_tags(&block[..end]));
+        rest = &block[end..];
+    }
+
+    examples
+}
+
+// An example input together with the answers the puzzle gives for it.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub answers: Vec<(u8, String)>,
+}
+
+enum Token {
+    Block(String),
+    Answer(String),
+}
+
+// Example blocks and emphasized `<code><em>` values of `article`, in order.
+fn tokens(article: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = article;
+
+    loop {
+        let block = rest.find("<pre><code>");
+        let answer = rest.find("<code><em>");
+
+        let (start, open, close) = match (block, answer) {
+            (Some(block), Some(answer)) if block < answer => (block, "<pre><code>", "</code></pre>"),
+            (Some(block), None) => (block, "<pre><code>", "</code></pre>"),
+            (_, Some(answer)) => (answer, "<code><em>", "</em></code>"),
+            (None, None) => break,
+        };
+
+        let contents = &rest[start + open.len()..];
+        let Some(end) = contents.find(close) else {
+            break;
+        };
+        let text = strip_tags(&contents[..end]);
+
+        tokens.push(if open == "<pre><code>" { Token::Block(text) } else { Token::Answer(text) });
+        rest = &contents[end + close.len()..];
+    }
+
+    tokens
+}
+
+// Pairs the examples of every part with their expected answers. The answer to
+// an example is taken to be the last emphasized value following it within
+// the same part, and a part without examples of its own is taken to give the
+// answer to the last example before it. Wrong guesses are easily fixed in the
+// written fixtures.
+pub fn examples_with_answers(html: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    let mut rest = html;
+    let mut part = 0;
+
+    while let Some(start) = rest.find("<article") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        rest = &article[end..];
+        part += 1;
+
+        let mut current: Option<usize> = None;
+        let mut answer = None;
+        for token in tokens(&article[..end]) {
+            match token {
+                Token::Block(input) => {
+                    if let (Some(i), Some(answer)) = (current, answer.take()) {
+                        examples[i].answers.push((part, answer));
+                    }
+                    examples.push(Example { input, answers: Vec::new() });
+                    current = Some(examples.len() - 1);
+                }
+                Token::Answer(value) => {
+                    if current.is_none() && !examples.is_empty() {
+                        current = Some(examples.len() - 1);
+                    }
+                    answer = Some(value);
+                }
+            }
+        }
+        if let (Some(i), Some(answer)) = (current, answer) {
+            examples[i].answers.push((part, answer));
+        }
+    }
+
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "\
+<main>
+<article class=\"day-desc\"><h2>--- Day 7: Camel Cards ---</h2><p>Here is an example:</p>
+<pre><code>32T3K 765
+T55J5 684
+</code></pre>
+<p>So, the first hand has bid <code>765</code>, for a total of <code><em>6440</em></code>.</p>
+<pre><code><em>A</em>&lt;B&gt; &amp; C
+</code></pre>
+</article>
+</main>";
+
+    #[test]
+    fn test_title() {
+        assert_eq!(Some("Camel Cards".into()), title(PAGE));
+        assert_eq!(None, title("<h2>Camel Cards</h2>"));
+        assert_eq!(None, title("<p>no heading</p>"));
+    }
+
+    // Shaped like the day 8 page: two examples in part 1 and a new one in part 2.
+    const TWO_PARTS: &str = "\
+<main>
+<article class=\"day-desc\"><h2>--- Day 8: Haunted Wasteland ---</h2>
+<pre><code>RL
+
+AAA = (BBB, CCC)
+</code></pre>
+<p>Starting with <code>AAA</code>, you need to look up the next element, <code><em>2</em></code> steps.</p>
+<pre><code>LLR
+
+AAA = (BBB, BBB)
+</code></pre>
+<p>Here, <code><em>6</em></code> steps.</p>
+</article>
+<p>Your puzzle answer was <code>12345</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<pre><code>LR
+
+11A = (11B, XXX)
+</code></pre>
+<p>So, in this example, you end up entirely on nodes that end in <code>Z</code> after <code><em>6</em></code> steps.</p>
+</article>
+</main>";
+
+    #[test]
+    fn test_examples_with_answers() {
+        assert_eq!(
+            vec![
+                Example { input: "RL\n\nAAA = (BBB, CCC)\n".into(), answers: vec![(1, "2".into())] },
+                Example { input: "LLR\n\nAAA = (BBB, BBB)\n".into(), answers: vec![(1, "6".into())] },
+                Example { input: "LR\n\n11A = (11B, XXX)\n".into(), answers: vec![(2, "6".into())] },
+            ],
+            examples_with_answers(TWO_PARTS),
+        );
+
+        // A part 2 without examples of its own reuses the last one of part 1.
+        let page = "\
+<article><pre><code>32T3K 765
+</code></pre><p>Total winnings of <code><em>6440</em></code>.</p></article>
+<article><p>Now <code>J</code> cards are jokers, total winnings are <code><em>5905</em></code>.</p></article>";
+        assert_eq!(
+            vec![Example { input: "32T3K 765\n".into(), answers: vec![(1, "6440".into()), (2, "5905".into())] }],
+            examples_with_answers(page),
+        );
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(vec!["32T3K 765\nT55J5 684\n".to_string(), "A<B> & C\n".to_string()], examples(PAGE));
+        assert!(examples("<pre><code>unterminated").is_empty());
+    }
+}
@@ -0,0 +1,39 @@
+use aoc_core::Day;
+
+pub static DAYS: [Day; 10] = [
+    Day::new::<day_1::calibration::Document>(1, "Trebuchet?!").with_lint::<day_1::calibration::Document>(),
+    Day::new::<day_2::game::Games>(2, "Cube Conundrum"),
+    Day::new::<day_3::engine_schematic::EngineSchematic>(3, "Gear Ratios")
+        .with_render::<day_3::engine_schematic::EngineSchematic>(),
+    Day::new::<day_4::card::Scratchcards>(4, "Scratchcards").with_lint::<day_4::card::Scratchcards>(),
+    Day::new::<day_5::almanac::Almanac>(5, "If You Give A Seed A Fertilizer").with_lint::<day_5::almanac::Almanac>(),
+    Day::new::<day_6::race::Races>(6, "Wait For It"),
+    Day::new::<day_7::camel_cards::CamelCards>(7, "Camel Cards"),
+    Day::new::<day_8::network::Network>(8, "Haunted Wasteland").with_lint::<day_8::network::Network>(),
+    Day::new::<day_9::oasis::Report>(9, "Mirage Maintenance"),
+    Day::new::<day_10::grid::Grid>(10, "Pipe Maze")
+        .with_render::<day_10::grid::Grid>()
+        .with_lint::<day_10::grid::Grid>(),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_ordered_and_unique() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(i + 1, day.number as usize);
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!("Camel Cards", find(7).unwrap().title);
+        assert!(find(DAYS.len() as u8 + 1).is_none());
+    }
+}
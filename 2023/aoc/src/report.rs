@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use aoc_core::Answer;
+use serde::Serialize;
+
+use crate::answers::{self, YEAR};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    Solved,
+    Unsolved,
+    InputError,
+    ParseError,
+}
+
+// The outcome of a part of a day, printed as a line of JSON for scripts to
+// consume. Answers are strings, as numbers may not fit a double. Times are in
+// microseconds, and the input hash is the one keying the answers file.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub input_hash: Option<String>,
+    pub parse_time_us: Option<u64>,
+    pub solve_time_us: Option<u64>,
+    pub error: Option<String>,
+}
+
+fn micros(duration: Duration) -> u64 {
+    duration.as_micros().try_into().unwrap_or(u64::MAX)
+}
+
+impl Record {
+    pub fn solved(day: u8, part: u8, input: &str, answer: &Answer, parse_time: Duration, solve_time: Duration) -> Self {
+        let (status, answer) = match answer {
+            Answer::Unsolved => (Status::Unsolved, None),
+            answer => (Status::Solved, Some(answer.to_string())),
+        };
+
+        Self {
+            year: YEAR,
+            day,
+            part,
+            status,
+            answer,
+            input_hash: Some(format!("{:016x}", answers::input_hash(input))),
+            parse_time_us: Some(micros(parse_time)),
+            solve_time_us: Some(micros(solve_time)),
+            error: None,
+        }
+    }
+
+    // The input of the day could not be read, so none of its parts were solved.
+    pub fn input_error(day: u8, part: u8, error: &dyn std::error::Error) -> Self {
+        Self {
+            year: YEAR,
+            day,
+            part,
+            status: Status::InputError,
+            answer: None,
+            input_hash: None,
+            parse_time_us: None,
+            solve_time_us: None,
+            error: Some(error.to_string()),
+        }
+    }
+
+    pub fn parse_error(day: u8, part: u8, input: &str, parse_time: Duration, error: &dyn std::error::Error) -> Self {
+        Self {
+            status: Status::ParseError,
+            input_hash: Some(format!("{:016x}", answers::input_hash(input))),
+            parse_time_us: Some(micros(parse_time)),
+            ..Self::input_error(day, part, error)
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records always serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_to_json() {
+        let record = Record::solved(7, 2, "32T3K 765\n", &Answer::Number(5905), Duration::from_micros(12), Duration::from_nanos(3400));
+        assert_eq!(
+            format!(
+                "{{\"year\":2023,\"day\":7,\"part\":2,\"status\":\"solved\",\"answer\":\"5905\",\"input_hash\":\"{:016x}\",\"parse_time_us\":12,\"solve_time_us\":3,\"error\":null}}",
+                answers::input_hash("32T3K 765\n"),
+            ),
+            record.to_json(),
+        );
+
+        let record = Record::solved(1, 1, "", &Answer::Unsolved, Duration::ZERO, Duration::ZERO);
+        assert_eq!(Status::Unsolved, record.status);
+        assert_eq!(None, record.answer);
+    }
+
+    #[test]
+    fn test_error_records() {
+        let error = std::io::Error::other("line 1, column 3: invalid \"x\"");
+
+        let record = Record::input_error(3, 1, &error);
+        assert_eq!(
+            "{\"year\":2023,\"day\":3,\"part\":1,\"status\":\"input-error\",\"answer\":null,\"input_hash\":null,\"parse_time_us\":null,\"solve_time_us\":null,\"error\":\"line 1, column 3: invalid \\\"x\\\"\"}",
+            record.to_json(),
+        );
+
+        let record = Record::parse_error(3, 2, "x", Duration::from_micros(5), &error);
+        assert_eq!(Status::ParseError, record.status);
+        assert_eq!(Some(5), record.parse_time_us);
+        assert_eq!(None, record.solve_time_us);
+        assert!(record.input_hash.is_some());
+    }
+}
@@ -0,0 +1,421 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// A new day crate laid out like the existing ones, registered with the
+// workspace and the runner.
+#[derive(Debug)]
+pub struct Scaffold {
+    pub day: u8,
+    pub title: String,
+    pub module: String,
+    pub example: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Exists(PathBuf),
+    OutOfOrder { day: u8, next: u8 },
+    Io(PathBuf, io::Error),
+    Unregistrable(PathBuf),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Exists(path) => write!(f, "{} already exists", path.display()),
+            Error::OutOfOrder { day, next } => write!(f, "cannot add day {}, the next day to add is {}", day, next),
+            Error::Io(path, err) => write!(f, "could not access {}: {}", path.display(), err),
+            Error::Unregistrable(path) => write!(f, "could not find where to register the new day in {}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// "Cosmic Expansion" becomes "cosmic_expansion".
+pub fn module_name(title: &str) -> String {
+    let words = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+
+    match words.first() {
+        Some(first) if !first.starts_with(|c: char| c.is_ascii_digit()) => words.join("_"),
+        _ => "puzzle".to_string(),
+    }
+}
+
+// "cosmic_expansion" becomes "CosmicExpansion".
+fn type_name(module: &str) -> String {
+    module
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+fn string_literal(text: &str) -> String {
+    let escaped = text.trim_end_matches('\n').replace('\\', "\\\\").replace('"', "\\\"");
+
+    // A continuation would swallow the leading whitespace of the first line.
+    if escaped.starts_with(char::is_whitespace) {
+        format!("\"{}\"", escaped)
+    } else {
+        format!("\"\\\n{}\"", escaped)
+    }
+}
+
+impl Scaffold {
+    fn type_name(&self) -> String {
+        type_name(&self.module)
+    }
+
+    fn cargo_toml(&self) -> String {
+        format!(
+            "\
+[package]
+name = \"day-{}\"
+version.workspace = true
+edition.workspace = true
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-core.workspace = true
+",
+            self.day,
+        )
+    }
+
+    fn lib_rs(&self) -> String {
+        format!("pub mod {};\n", self.module)
+    }
+
+    fn main_rs(&self) -> String {
+        format!(
+            "\
+use aoc_core::{{input, parse, Solution}};
+use day_{day}::{module}::{type_name};
+
+fn main() {{
+    let input = input::from_args_or_exit({day});
+    let mut {module} = parse::or_exit({day}, {type_name}::parse(&input));
+
+    println!(\"part 1: {{}}\", {module}.part1());
+    println!(\"part 2: {{}}\", {module}.part2());
+}}
+",
+            day = self.day,
+            module = self.module,
+            type_name = self.type_name(),
+        )
+    }
+
+    fn module_rs(&self) -> String {
+        let type_name = self.type_name();
+
+        let example_test = match &self.example {
+            Some(example) => format!(
+                "
+
+    const EXAMPLE: &str = {example};
+
+    #[test]
+    fn test_{module}_from_str() {{
+        assert!(EXAMPLE.parse::<{type_name}>().is_ok());
+    }}",
+                example = string_literal(example),
+                module = self.module,
+                type_name = type_name,
+            ),
+            None => String::new(),
+        };
+
+        format!(
+            "\
+use std::fmt;
+use std::str::FromStr;
+use aoc_core::Solution;
+
+pub struct {type_name};
+
+#[derive(Debug, PartialEq)]
+pub enum ErrorKind {{
+    Empty,
+}}
+
+impl fmt::Display for ErrorKind {{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {{
+        match self {{
+            ErrorKind::Empty => write!(f, \"empty input\"),
+        }}
+    }}
+}}
+
+pub type ParseError = aoc_core::ParseError<ErrorKind>;
+
+impl FromStr for {type_name} {{
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {{
+        if s.trim().is_empty() {{
+            return Err(ParseError::new(1, 1, ErrorKind::Empty));
+        }}
+
+        Ok({type_name})
+    }}
+}}
+
+impl Solution for {type_name} {{
+    type Error = ParseError;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> {{
+        input.parse()
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;{example_test}
+
+    #[test]
+    fn test_{module}_from_empty_str() {{
+        let err = \"\".parse::<{type_name}>().err();
+        assert_eq!(Some(ParseError::new(1, 1, ErrorKind::Empty)), err);
+    }}
+}}
+",
+            type_name = type_name,
+            module = self.module,
+            example_test = example_test,
+        )
+    }
+
+    // Adds `"<day>"` to the workspace members and `day-<day>` to its dependencies.
+    fn register_in_workspace(&self, manifest: &str) -> Option<String> {
+        let mut lines = manifest.lines().map(String::from).collect::<Vec<_>>();
+
+        let last_member = lines.iter().rposition(|line| {
+            let member = line.trim().trim_end_matches(',').trim_matches('"');
+            line.trim().starts_with('"') && member.parse::<u8>().is_ok()
+        })?;
+        lines.insert(last_member + 1, format!("    \"{}\",", self.day));
+
+        let last_day = lines.iter().rposition(|line| line.starts_with("day-"))?;
+        lines.insert(last_day + 1, format!("day-{} = {{ path = \"{}\" }}", self.day, self.day));
+
+        Some(lines.join("\n") + "\n")
+    }
+
+    // Adds `day-<day>` to the dependencies of the runner.
+    fn register_in_runner_manifest(&self, manifest: &str) -> Option<String> {
+        let mut lines = manifest.lines().map(String::from).collect::<Vec<_>>();
+
+        let last_day = lines.iter().rposition(|line| line.starts_with("day-"))?;
+        lines.insert(last_day + 1, format!("day-{}.workspace = true", self.day));
+
+        Some(lines.join("\n") + "\n")
+    }
+
+    // Appends the day to `DAYS`, which must hold every day before it.
+    fn register_in_registry(&self, registry: &str) -> Result<String, Option<Error>> {
+        let start = registry.find("pub static DAYS: [Day; ").ok_or(None)? + "pub static DAYS: [Day; ".len();
+        let end = start + registry[start..].find(']').ok_or(None)?;
+        let count = registry[start..end].parse::<u8>().map_err(|_| None)?;
+
+        if self.day != count + 1 {
+            return Err(Some(Error::OutOfOrder { day: self.day, next: count + 1 }));
+        }
+
+        let close = end + registry[end..].find("\n];").ok_or(None)?;
+        let entry = format!(
+            "\n    Day::new::<day_{}::{}::{}>({}, {:?}),",
+            self.day,
+            self.module,
+            self.type_name(),
+            self.day,
+            self.title,
+        );
+
+        Ok(format!("{}{}{}{}{}", &registry[..start], self.day, &registry[end..close], entry, &registry[close..]))
+    }
+
+    // Creates the crate in `<workspace>/<day>` and registers it, returning the
+    // files created or changed. Registration is worked out before anything
+    // gets written, so a failure leaves the workspace untouched.
+    pub fn create(&self, workspace: &Path) -> Result<Vec<PathBuf>, Error> {
+        let crate_dir = workspace.join(self.day.to_string());
+        if crate_dir.exists() {
+            return Err(Error::Exists(crate_dir));
+        }
+
+        let read = |path: PathBuf| match std::fs::read_to_string(&path) {
+            Ok(text) => Ok((path, text)),
+            Err(err) => Err(Error::Io(path, err)),
+        };
+        let (workspace_path, workspace_manifest) = read(workspace.join("Cargo.toml"))?;
+        let (runner_path, runner_manifest) = read(workspace.join("aoc").join("Cargo.toml"))?;
+        let (registry_path, registry) = read(workspace.join("aoc").join("src").join("registry.rs"))?;
+
+        let registry = self
+            .register_in_registry(&registry)
+            .map_err(|err| err.unwrap_or_else(|| Error::Unregistrable(registry_path.clone())))?;
+        let workspace_manifest = self
+            .register_in_workspace(&workspace_manifest)
+            .ok_or_else(|| Error::Unregistrable(workspace_path.clone()))?;
+        let runner_manifest = self
+            .register_in_runner_manifest(&runner_manifest)
+            .ok_or_else(|| Error::Unregistrable(runner_path.clone()))?;
+
+        let src_dir = crate_dir.join("src");
+        std::fs::create_dir_all(&src_dir).map_err(|err| Error::Io(src_dir.clone(), err))?;
+
+        let files = [
+            (crate_dir.join("Cargo.toml"), self.cargo_toml()),
+            (src_dir.join("lib.rs"), self.lib_rs()),
+            (src_dir.join("main.rs"), self.main_rs()),
+            (src_dir.join(format!("{}.rs", self.module)), self.module_rs()),
+            (workspace_path, workspace_manifest),
+            (runner_path, runner_manifest),
+            (registry_path, registry),
+        ];
+        for (path, contents) in &files {
+            std::fs::write(path, contents).map_err(|err| Error::Io(path.clone(), err))?;
+        }
+
+        Ok(files.into_iter().map(|(path, _)| path).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE_MANIFEST: &str = "\
+[workspace]
+members = [
+    \"1\",
+    \"2\",
+    \"aoc\",
+]
+
+[workspace.dependencies]
+aoc-core = { path = \"core\" }
+day-1 = { path = \"1\" }
+day-2 = { path = \"2\" }
+itertools = \"0.12.0\"
+";
+
+    const RUNNER_MANIFEST: &str = "\
+[dependencies]
+aoc-core.workspace = true
+day-1.workspace = true
+day-2.workspace = true
+";
+
+    const REGISTRY: &str = "\
+use aoc_core::Day;
+
+pub static DAYS: [Day; 2] = [
+    Day::new::<day_1::calibration::Document>(1, \"Trebuchet?!\"),
+    Day::new::<day_2::game::Games>(2, \"Cube Conundrum\"),
+];
+";
+
+    fn scaffold(day: u8, example: Option<&str>) -> Scaffold {
+        Scaffold {
+            day,
+            title: "Gear Ratios".into(),
+            module: module_name("Gear Ratios"),
+            example: example.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!("cosmic_expansion", module_name("Cosmic Expansion"));
+        assert_eq!("trebuchet", module_name("Trebuchet?!"));
+        assert_eq!("if_you_give_a_seed_a_fertilizer", module_name("If You Give A Seed A Fertilizer"));
+        assert_eq!("puzzle", module_name("1000 Lights"));
+        assert_eq!("puzzle", module_name(""));
+
+        assert_eq!("CosmicExpansion", type_name("cosmic_expansion"));
+    }
+
+    #[test]
+    fn test_string_literal() {
+        assert_eq!("\"\\\n467..114..\n...*......\"", string_literal("467..114..\n...*......\n"));
+        assert_eq!("\"  \\\"a\\\\b\\\"\"", string_literal("  \"a\\b\"\n"));
+    }
+
+    #[test]
+    fn test_register() {
+        let day_3 = scaffold(3, None);
+
+        let workspace = day_3.register_in_workspace(WORKSPACE_MANIFEST).unwrap();
+        assert!(workspace.contains("    \"2\",\n    \"3\",\n    \"aoc\","));
+        assert!(workspace.contains("day-2 = { path = \"2\" }\nday-3 = { path = \"3\" }\nitertools"));
+
+        let runner = day_3.register_in_runner_manifest(RUNNER_MANIFEST).unwrap();
+        assert!(runner.ends_with("day-2.workspace = true\nday-3.workspace = true\n"));
+
+        let registry = day_3.register_in_registry(REGISTRY).unwrap();
+        assert!(registry.contains("pub static DAYS: [Day; 3] = ["));
+        assert!(registry.contains(
+            "(2, \"Cube Conundrum\"),\n    Day::new::<day_3::gear_ratios::GearRatios>(3, \"Gear Ratios\"),\n];\n"
+        ));
+
+        match scaffold(4, None).register_in_registry(REGISTRY) {
+            Err(Some(Error::OutOfOrder { day, next })) => assert_eq!((4, 3), (day, next)),
+            _ => panic!("expected an out of order error"),
+        }
+        assert!(matches!(day_3.register_in_registry("pub static DAYS = [];"), Err(None)));
+    }
+
+    #[test]
+    fn test_module_rs() {
+        let module = scaffold(3, Some("467..114..\n...*......\n")).module_rs();
+
+        assert!(module.contains("pub struct GearRatios;"));
+        assert!(module.contains("impl Solution for GearRatios {"));
+        assert!(module.contains("    const EXAMPLE: &str = \"\\\n467..114..\n...*......\";"));
+        assert!(module.contains("fn test_gear_ratios_from_str()"));
+
+        assert!(!scaffold(3, None).module_rs().contains("EXAMPLE"));
+    }
+
+    #[test]
+    fn test_create() {
+        let workspace = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        std::fs::create_dir_all(workspace.join("aoc").join("src")).unwrap();
+        std::fs::write(workspace.join("Cargo.toml"), WORKSPACE_MANIFEST).unwrap();
+        std::fs::write(workspace.join("aoc").join("Cargo.toml"), RUNNER_MANIFEST).unwrap();
+        std::fs::write(workspace.join("aoc").join("src").join("registry.rs"), REGISTRY).unwrap();
+
+        // Nothing gets written when the day cannot be registered.
+        assert!(matches!(scaffold(4, None).create(&workspace), Err(Error::OutOfOrder { .. })));
+        assert!(!workspace.join("4").exists());
+        assert_eq!(WORKSPACE_MANIFEST, std::fs::read_to_string(workspace.join("Cargo.toml")).unwrap());
+
+        let files = scaffold(3, None).create(&workspace).unwrap();
+        assert_eq!(7, files.len());
+        assert_eq!(
+            "pub mod gear_ratios;\n",
+            std::fs::read_to_string(workspace.join("3").join("src").join("lib.rs")).unwrap(),
+        );
+        assert!(std::fs::read_to_string(workspace.join("3").join("src").join("main.rs"))
+            .unwrap()
+            .contains("parse::or_exit(3, GearRatios::parse(&input));"));
+
+        assert!(matches!(scaffold(3, None).create(&workspace), Err(Error::Exists(_))));
+
+        std::fs::remove_dir_all(workspace).unwrap();
+    }
+}
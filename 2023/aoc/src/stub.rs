@@ -0,0 +1,31 @@
+// A local HTTP server standing in for adventofcode.com in tests.
+
+use std::thread;
+
+// Serves `responses` to the first requests made to it and returns its base URL
+// together with the requests it has seen, as "METHOD url cookie body".
+pub fn stub_server(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+    let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+    let handle = thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (code, body) in responses {
+            let mut request = server.recv().unwrap();
+
+            let cookie = request.headers()
+                .iter()
+                .find(|header| header.field.equiv("Cookie"))
+                .map(|header| header.value.to_string())
+                .unwrap_or_default();
+            let mut content = String::new();
+            request.as_reader().read_to_string(&mut content).unwrap();
+            requests.push(format!("{} {} {} {}", request.method(), request.url(), cookie, content).trim().to_string());
+
+            request.respond(tiny_http::Response::from_string(body).with_status_code(code)).unwrap();
+        }
+        requests
+    });
+
+    (base_url, handle)
+}
@@ -0,0 +1,406 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::answers::YEAR;
+use crate::client::{self, Client};
+use crate::html;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited(wait) => write!(f, "rate limited, {}s left to wait", wait.as_secs()),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+impl Outcome {
+    fn is_wrong(&self) -> bool {
+        matches!(self, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong)
+    }
+
+    // Single word used in the submissions log.
+    fn token(&self) -> String {
+        match self {
+            Outcome::Correct => "correct".into(),
+            Outcome::TooHigh => "too-high".into(),
+            Outcome::TooLow => "too-low".into(),
+            Outcome::Wrong => "wrong".into(),
+            Outcome::RateLimited(wait) => format!("rate-limited:{}", wait.as_secs()),
+            Outcome::AlreadySolved => "already-solved".into(),
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Self> {
+        match token {
+            "correct" => Some(Outcome::Correct),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            "wrong" => Some(Outcome::Wrong),
+            "already-solved" => Some(Outcome::AlreadySolved),
+            _ => {
+                let seconds = token.strip_prefix("rate-limited:")?.parse().ok()?;
+                Some(Outcome::RateLimited(Duration::from_secs(seconds)))
+            }
+        }
+    }
+}
+
+// The text of the <article> holding the server's reply, without its markup.
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article>"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+
+    html::strip_tags(article).split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Parses the "1h 4m 37s" in "You have 1h 4m 37s left to wait".
+fn wait_time(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+
+    let mut seconds = 0;
+    for amount in text[start..end].split_whitespace() {
+        let unit = match amount.chars().last()? {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        seconds += amount[..amount.len() - 1].parse::<u64>().ok()? * unit;
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+pub fn classify(html: &str) -> Option<Outcome> {
+    let text = article_text(html);
+
+    if text.starts_with("That's the right answer") {
+        Some(Outcome::Correct)
+    } else if text.starts_with("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Some(Outcome::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Outcome::TooLow)
+        } else {
+            Some(Outcome::Wrong)
+        }
+    } else if text.starts_with("You gave an answer too recently") {
+        Some(Outcome::RateLimited(wait_time(&text)?))
+    } else if text.starts_with("You don't seem to be solving the right level") {
+        Some(Outcome::AlreadySolved)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+impl Entry {
+    // Whether this past submission shows that `answer` cannot be right.
+    fn rules_out(&self, answer: &str) -> bool {
+        if self.answer == answer {
+            return self.outcome.is_wrong();
+        }
+
+        match (self.answer.parse::<i128>(), answer.parse::<i128>()) {
+            (Ok(bound), Ok(answer)) => match self.outcome {
+                Outcome::TooHigh => answer >= bound,
+                Outcome::TooLow => answer <= bound,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Client(client::Error),
+    Log(PathBuf, io::Error),
+    MalformedLog { path: PathBuf, line: usize },
+    RuledOut(Entry),
+    Solved(Entry),
+    UnrecognizedResponse(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Client(err) => write!(f, "{}", err),
+            Error::Log(path, err) => write!(f, "could not access {}: {}", path.display(), err),
+            Error::MalformedLog { path, line } => write!(
+                f,
+                "{}:{}: expected \"<year> <day> <part> <outcome> <answer>\"",
+                path.display(),
+                line,
+            ),
+            Error::RuledOut(entry) => write!(f, "not submitting, {} was {}", entry.answer, entry.outcome),
+            Error::Solved(entry) => write!(f, "not submitting, already solved with {}", entry.answer),
+            Error::UnrecognizedResponse(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<client::Error> for Error {
+    fn from(err: client::Error) -> Self {
+        Error::Client(err)
+    }
+}
+
+// Every submission made so far, appended one per line as
+// `<year> <day> <part> <outcome> <answer>`.
+#[derive(Debug)]
+pub struct Log {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Log {
+    // Submissions are logged next to the inputs they were made for.
+    pub fn default_path(input_dir: &Path) -> PathBuf {
+        input_dir.join("submissions.txt")
+    }
+
+    // A missing file is an empty log, it gets created by `append`.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(Error::Log(path.to_path_buf(), err)),
+        };
+
+        let mut entries = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match parse_line(line) {
+                Some((YEAR, entry)) => entries.push(entry),
+                Some(_) => {}
+                None => return Err(Error::MalformedLog { path: path.to_path_buf(), line: i + 1 }),
+            }
+        }
+
+        Ok(Self { path: path.to_path_buf(), entries })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    // The answer the server accepted for a part, if any.
+    pub fn accepted(&self, day: u8, part: u8) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|entry| entry.day == day && entry.part == part && entry.outcome == Outcome::Correct)
+            .map(|entry| entry.answer.as_str())
+    }
+
+    // Fails when a past submission shows that `answer` is not worth submitting.
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Result<(), Error> {
+        let entries = || self.entries.iter().filter(|entry| entry.day == day && entry.part == part);
+
+        if let Some(entry) = entries().find(|entry| entry.outcome == Outcome::Correct) {
+            return Err(Error::Solved(entry.clone()));
+        }
+        match entries().find(|entry| entry.rules_out(answer)) {
+            Some(entry) => Err(Error::RuledOut(entry.clone())),
+            None => Ok(()),
+        }
+    }
+
+    pub fn append(&mut self, entry: Entry) -> Result<(), Error> {
+        let line = format!("{} {} {} {} {}\n", YEAR, entry.day, entry.part, entry.outcome.token(), entry.answer);
+
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|err| Error::Log(self.path.clone(), err))?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str) -> Option<(u16, Entry)> {
+    let mut fields = line.splitn(5, ' ');
+
+    let year = fields.next()?.parse().ok()?;
+    let entry = Entry {
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        outcome: Outcome::from_token(fields.next()?)?,
+        answer: fields.next().filter(|answer| !answer.is_empty())?.to_string(),
+    };
+
+    Some((year, entry))
+}
+
+// Submits `answer` unless the log already rules it out, and logs the outcome.
+pub fn submit(client: &Client, log: &mut Log, day: u8, part: u8, answer: &str) -> Result<Outcome, Error> {
+    log.check(day, part, answer)?;
+
+    let html = client.answer(day, part, answer)?;
+    let outcome = classify(&html).ok_or_else(|| Error::UnrecognizedResponse(article_text(&html)))?;
+
+    log.append(Entry { day, part, answer: answer.to_string(), outcome })?;
+
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stub::stub_server;
+
+    const CORRECT: &str = include_str!("../testdata/submit/correct.html");
+    const TOO_HIGH: &str = include_str!("../testdata/submit/too_high.html");
+    const TOO_LOW: &str = include_str!("../testdata/submit/too_low.html");
+    const WRONG: &str = include_str!("../testdata/submit/wrong.html");
+    const RATE_LIMITED: &str = include_str!("../testdata/submit/rate_limited.html");
+    const ALREADY_SOLVED: &str = include_str!("../testdata/submit/already_solved.html");
+
+    fn temp_log(name: &str) -> Log {
+        let path = std::env::temp_dir().join(format!("aoc-submissions-{}-{}.txt", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        Log::open(&path).unwrap()
+    }
+
+    fn entry(answer: &str, outcome: Outcome) -> Entry {
+        Entry { day: 5, part: 2, answer: answer.into(), outcome }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(Some(Outcome::Correct), classify(CORRECT));
+        assert_eq!(Some(Outcome::TooHigh), classify(TOO_HIGH));
+        assert_eq!(Some(Outcome::TooLow), classify(TOO_LOW));
+        assert_eq!(Some(Outcome::Wrong), classify(WRONG));
+        assert_eq!(Some(Outcome::RateLimited(Duration::from_secs(4 * 60 + 37))), classify(RATE_LIMITED));
+        assert_eq!(Some(Outcome::AlreadySolved), classify(ALREADY_SOLVED));
+
+        assert_eq!(None, classify("<html><body>Internal Server Error</body></html>"));
+    }
+
+    #[test]
+    fn test_wait_time() {
+        assert_eq!(Some(Duration::from_secs(37)), wait_time("You have 37s left to wait."));
+        assert_eq!(Some(Duration::from_secs(3600 + 60)), wait_time("You have 1h 1m left to wait."));
+        assert_eq!(None, wait_time("You have a while left to wait."));
+    }
+
+    #[test]
+    fn test_outcome_tokens() {
+        for outcome in [
+            Outcome::Correct,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Wrong,
+            Outcome::RateLimited(Duration::from_secs(277)),
+            Outcome::AlreadySolved,
+        ] {
+            assert_eq!(Some(outcome), Outcome::from_token(&outcome.token()));
+        }
+    }
+
+    #[test]
+    fn test_entry_rules_out() {
+        assert!(entry("52", Outcome::Wrong).rules_out("52"));
+        assert!(!entry("52", Outcome::Wrong).rules_out("51"));
+
+        assert!(entry("52", Outcome::TooHigh).rules_out("60"));
+        assert!(!entry("52", Outcome::TooHigh).rules_out("46"));
+        assert!(entry("40", Outcome::TooLow).rules_out("12"));
+        assert!(!entry("40", Outcome::TooLow).rules_out("46"));
+
+        assert!(!entry("52", Outcome::RateLimited(Duration::from_secs(30))).rules_out("52"));
+    }
+
+    #[test]
+    fn test_log_round_trip() {
+        let mut log = temp_log("round-trip");
+        log.append(entry("52", Outcome::TooHigh)).unwrap();
+        log.append(entry("46", Outcome::RateLimited(Duration::from_secs(60)))).unwrap();
+
+        let log = Log::open(&log.path).unwrap();
+        assert_eq!(&[entry("52", Outcome::TooHigh), entry("46", Outcome::RateLimited(Duration::from_secs(60)))], log.entries());
+
+        match log.check(5, 2, "53") {
+            Err(Error::RuledOut(ruled_out_by)) => assert_eq!(entry("52", Outcome::TooHigh), ruled_out_by),
+            _ => panic!("expected the answer to be ruled out"),
+        }
+        assert!(log.check(5, 2, "46").is_ok());
+        assert!(log.check(5, 1, "53").is_ok());
+        assert_eq!(None, log.accepted(5, 2));
+
+        std::fs::remove_file(&log.path).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let (base_url, server) = stub_server(vec![(200, TOO_LOW), (200, CORRECT)]);
+        let client = Client::new(&base_url, "53551");
+        let mut log = temp_log("submit");
+
+        assert_eq!(Outcome::TooLow, submit(&client, &mut log, 5, 2, "35").unwrap());
+        assert!(matches!(submit(&client, &mut log, 5, 2, "30"), Err(Error::RuledOut(_))));
+        assert_eq!(Outcome::Correct, submit(&client, &mut log, 5, 2, "46").unwrap());
+        assert!(matches!(submit(&client, &mut log, 5, 2, "46"), Err(Error::Solved(_))));
+
+        assert_eq!(
+            vec![
+                "POST /2023/day/5/answer session=53551 level=2&answer=35",
+                "POST /2023/day/5/answer session=53551 level=2&answer=46",
+            ],
+            server.join().unwrap(),
+        );
+        assert_eq!(&[entry("35", Outcome::TooLow), entry("46", Outcome::Correct)], log.entries());
+        assert_eq!(Some("46"), log.accepted(5, 2));
+
+        std::fs::remove_file(&log.path).unwrap();
+    }
+
+    #[test]
+    fn test_submit_unrecognized_response() {
+        let (base_url, server) = stub_server(vec![(200, "<article><p>Something new.</p></article>")]);
+        let mut log = temp_log("unrecognized");
+
+        match submit(&Client::new(&base_url, "53551"), &mut log, 5, 2, "46") {
+            Err(Error::UnrecognizedResponse(text)) => assert_eq!("Something new.", text),
+            _ => panic!("expected an unrecognized response"),
+        }
+        assert!(log.entries().is_empty());
+
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,663 @@
+// Puzzle inputs of a configurable size. `input` always gives the same input
+// for a scale, for benchmarking days whose real input is not available, while
+// `random` varies everything with its seed, for stress testing and for
+// sharing a reproducible input in place of a real one.
+
+const CALIBRATION_LINES: [&str; 7] = [
+    "two1nine",
+    "eight2three",
+    "abcone2threexyz",
+    "xtwone3four",
+    "4nineeightseven2",
+    "zoneight234",
+    "7pqrstsixteen",
+];
+
+const GAME_ROUNDS: [&str; 5] = [
+    "3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+    "1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+    "8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+    "1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+    "6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+];
+
+const ENGINE_SCHEMATIC: [&str; 10] = [
+    "467..114..",
+    "...*......",
+    "..35..633.",
+    "......#...",
+    "617*......",
+    ".....+.58.",
+    "..592.....",
+    "......755.",
+    "...$.*....",
+    ".664.598..",
+];
+
+// Linear congruential generator, good enough to vary the generated inputs.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, bound: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) % bound
+    }
+
+    fn range(&mut self, start: u64, end: u64) -> u64 {
+        start + self.next(end - start)
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next(items.len() as u64) as usize]
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.next(i as u64 + 1) as usize);
+        }
+    }
+}
+
+fn calibration_document(scale: usize) -> String {
+    (0..scale * CALIBRATION_LINES.len())
+        .map(|i| format!("{}\n", CALIBRATION_LINES[i % CALIBRATION_LINES.len()]))
+        .collect()
+}
+
+fn games(scale: usize) -> String {
+    (0..scale * GAME_ROUNDS.len())
+        .map(|i| format!("Game {}: {}\n", i + 1, GAME_ROUNDS[i % GAME_ROUNDS.len()]))
+        .collect()
+}
+
+fn engine_schematic(scale: usize) -> String {
+    (0..scale)
+        .flat_map(|_| ENGINE_SCHEMATIC.iter())
+        .map(|row| format!("{}\n", row.repeat(scale)))
+        .collect()
+}
+
+// Every third card wins copies of the next two, which do not win anything,
+// so that the number of copies stays linear in the number of cards.
+fn scratchcards(scale: usize) -> String {
+    let number_of_cards = scale * 9;
+
+    (1..=number_of_cards)
+        .map(|id| {
+            let matches = if id % 3 == 1 && id + 2 <= number_of_cards { 2 } else { 0 };
+            let winning_numbers = [41, 48, 83, 86, 17];
+            let mut numbers = [1, 2, 3, 4, 5, 6, 7, 8];
+            numbers[..matches].copy_from_slice(&winning_numbers[..matches]);
+
+            let winning_numbers = winning_numbers.map(|n| format!("{:2}", n)).join(" ");
+            let numbers = numbers.iter().map(|n| format!("{:2}", n)).collect::<Vec<_>>().join(" ");
+            format!("Card {}: {} | {}\n", id, winning_numbers, numbers)
+        })
+        .collect()
+}
+
+fn almanac(scale: usize) -> String {
+    let mut lcg = Lcg(5);
+    let mut almanac = String::from("seeds:");
+    for _ in 0..scale * 2 {
+        almanac.push_str(&format!(" {} {}", lcg.next(1 << 32), lcg.next(1 << 24) + 1));
+    }
+    almanac.push('\n');
+
+    let categories = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+    for names in categories.windows(2) {
+        almanac.push_str(&format!("\n{}-to-{} map:\n", names[0], names[1]));
+
+        // Consecutive, non overlapping source ranges shuffled onto random destinations.
+        let mut source_start = lcg.next(1 << 20);
+        for _ in 0..scale * 4 {
+            let length = lcg.next(1 << 28) + 1;
+            almanac.push_str(&format!("{} {} {}\n", lcg.next(1 << 32), source_start, length));
+            source_start += length + lcg.next(1 << 20);
+        }
+    }
+
+    almanac
+}
+
+// The races get kerned into a single race in part 2, so their number does not scale.
+fn races(_scale: usize) -> String {
+    String::from("Time:      44     82     69     81\nDistance:   202   1076   1138   1458\n")
+}
+
+fn camel_cards(scale: usize) -> String {
+    let symbols = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+    let mut lcg = Lcg(7);
+
+    (0..scale * 100)
+        .map(|_| {
+            let hand = (0..5).map(|_| symbols[lcg.next(symbols.len() as u64) as usize]).collect::<String>();
+            format!("{} {}\n", hand, lcg.next(1000) + 1)
+        })
+        .collect()
+}
+
+// Ghosts walking separate loops of different lengths, plus an AAA to ZZZ path.
+fn network(scale: usize) -> String {
+    let mut network = String::from("LRRL\n\n");
+    let ghost_names = ["AAA", "BBA", "CCA", "DDA", "EEA", "FFA"];
+    let loop_lengths = [13, 17, 19, 23, 29, 31];
+
+    for (ghost, name) in ghost_names.iter().enumerate() {
+        let prefix = &name[..2];
+        let length = loop_lengths[ghost] * scale;
+        let node = |step: usize| -> String {
+            match step {
+                0 => name.to_string(),
+                _ if step == length => if ghost == 0 { "ZZZ".to_string() } else { format!("{}Z", prefix) },
+                _ => format!("{}{}", prefix, step),
+            }
+        };
+
+        for step in 0..length {
+            let next = node(step + 1);
+            network.push_str(&format!("{} = ({}, {})\n", node(step), next, next));
+        }
+        network.push_str(&format!("{} = ({}, {})\n", node(length), node(1), node(1)));
+    }
+
+    network
+}
+
+// Polynomial sequences of degree at most 5.
+fn oasis_report(scale: usize) -> String {
+    let mut lcg = Lcg(9);
+
+    (0..scale * 50)
+        .map(|_| {
+            let coefficients = (0..=lcg.next(6)).map(|_| lcg.next(11) as i64 - 5).collect::<Vec<_>>();
+            let values = (0..21i64)
+                .map(|x| coefficients.iter().rev().fold(0, |value, c| value * x + c))
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>();
+            format!("{}\n", values.join(" "))
+        })
+        .collect()
+}
+
+// A rectangular loop enclosing a field of ground.
+fn pipe_maze(scale: usize) -> String {
+    let size = scale * 5 + 3;
+    let mut maze = String::new();
+
+    maze.push_str(&format!("S{}7\n", "-".repeat(size - 2)));
+    for _ in 0..size - 2 {
+        maze.push_str(&format!("|{}|\n", ".".repeat(size - 2)));
+    }
+    maze.push_str(&format!("L{}J\n", "-".repeat(size - 2)));
+
+    maze
+}
+
+// Input for `day` whose size grows linearly with `scale`, None for unknown days.
+pub fn input(day: u8, scale: usize) -> Option<String> {
+    let scale = scale.max(1);
+
+    let input = match day {
+        1 => calibration_document(scale),
+        2 => games(scale),
+        3 => engine_schematic(scale),
+        4 => scratchcards(scale),
+        5 => almanac(scale),
+        6 => races(scale),
+        7 => camel_cards(scale),
+        8 => network(scale),
+        9 => oasis_report(scale),
+        10 => pipe_maze(scale),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+const DIGIT_WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+// Letters with digits and spelled out digits mixed in, with at least one
+// digit per line for part 1.
+fn random_calibration_document(lcg: &mut Lcg, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let mut line = String::new();
+            let digits = lcg.range(1, 4);
+            let written = lcg.next(digits);
+            for digit in 0..digits {
+                for _ in 0..lcg.next(6) {
+                    line.push((b'a' + lcg.next(26) as u8) as char);
+                }
+                let value = lcg.range(1, 10) as usize;
+                if digit == written {
+                    line.push_str(&value.to_string());
+                } else if lcg.next(2) == 0 {
+                    line.push_str(DIGIT_WORDS[value - 1]);
+                }
+            }
+            format!("{}\n", line)
+        })
+        .collect()
+}
+
+fn random_games(lcg: &mut Lcg, size: usize) -> String {
+    (1..=size)
+        .map(|id| {
+            let rounds = (0..lcg.range(1, 7))
+                .map(|_| {
+                    let mut colors = ["red", "green", "blue"];
+                    lcg.shuffle(&mut colors);
+                    colors[..lcg.range(1, 4) as usize]
+                        .iter()
+                        .map(|color| format!("{} {}", lcg.range(1, 21), color))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect::<Vec<_>>();
+            format!("Game {}: {}\n", id, rounds.join("; "))
+        })
+        .collect()
+}
+
+// A square schematic with `size` rows, numbers are kept one cell apart from
+// each other so that they never run into one another.
+fn random_engine_schematic(lcg: &mut Lcg, size: usize) -> String {
+    let side = size.max(3);
+    let mut rows = vec![vec![b'.'; side]; side];
+
+    for row in rows.iter_mut() {
+        let mut j = lcg.next(4) as usize;
+        while j < side {
+            if lcg.next(6) == 0 {
+                row[j] = *lcg.pick(b"*#+$/@=%&-");
+                j += 2;
+                continue;
+            }
+
+            let number = lcg.range(1, 1000).to_string();
+            if j + number.len() > side {
+                break;
+            }
+            row[j..j + number.len()].copy_from_slice(number.as_bytes());
+            j += number.len() + 1 + lcg.next(4) as usize;
+        }
+    }
+
+    rows.into_iter().map(|row| format!("{}\n", String::from_utf8(row).unwrap())).collect()
+}
+
+// Cards of 10 winning numbers and 25 numbers. The copies won by the cards are
+// kept to about a hundred per card, as every copy gets expanded in part 2.
+fn random_scratchcards(lcg: &mut Lcg, size: usize) -> String {
+    let mut copies = vec![1usize; size];
+    let mut total = size;
+    let mut cards = String::new();
+
+    for id in 0..size {
+        let mut matches = lcg.next(11).min((size - id - 1) as u64) as usize;
+        if total + copies[id] * matches > 100 * size {
+            matches = 0;
+        }
+        for won in id + 1..=id + matches {
+            copies[won] += copies[id];
+        }
+        total += copies[id] * matches;
+
+        let mut pool = (1..100).collect::<Vec<u64>>();
+        lcg.shuffle(&mut pool);
+        let (winning_numbers, others) = pool.split_at(10);
+        let mut numbers = winning_numbers[..matches].iter().chain(&others[..25 - matches]).collect::<Vec<_>>();
+        lcg.shuffle(&mut numbers);
+
+        let winning_numbers = winning_numbers.iter().map(|n| format!("{:2}", n)).collect::<Vec<_>>().join(" ");
+        let numbers = numbers.iter().map(|n| format!("{:2}", n)).collect::<Vec<_>>().join(" ");
+        cards.push_str(&format!("Card {:3}: {} | {}\n", id + 1, winning_numbers, numbers));
+    }
+
+    cards
+}
+
+// Every map moves consecutive source ranges onto the same ranges in a
+// different order, like the real almanacs do.
+fn random_almanac(lcg: &mut Lcg, size: usize) -> String {
+    let mut almanac = String::from("seeds:");
+    for _ in 0..size.div_ceil(20) * 2 {
+        almanac.push_str(&format!(" {} {}", lcg.next(1 << 32), lcg.range(1, 1 << 28)));
+    }
+    almanac.push('\n');
+
+    let categories = ["seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location"];
+    for names in categories.windows(2) {
+        almanac.push_str(&format!("\n{}-to-{} map:\n", names[0], names[1]));
+
+        let lengths = (0..size.div_ceil(7)).map(|_| lcg.range(1, 1 << 28)).collect::<Vec<_>>();
+        let mut order = (0..lengths.len()).collect::<Vec<_>>();
+        lcg.shuffle(&mut order);
+
+        let mut source_starts = vec![0; lengths.len()];
+        let mut source_start = lcg.next(1 << 30);
+        for (i, length) in lengths.iter().enumerate() {
+            source_starts[i] = source_start;
+            source_start += length;
+        }
+        let mut destination_start = lcg.next(1 << 30);
+        for i in order {
+            almanac.push_str(&format!("{} {} {}\n", destination_start, source_starts[i], lengths[i]));
+            destination_start += lengths[i];
+        }
+    }
+
+    almanac
+}
+
+// At most 4 races, like the real ones, so that the kerned race fits in a u64.
+fn random_races(lcg: &mut Lcg, size: usize) -> String {
+    let races = (0..size.min(4))
+        .map(|_| {
+            let time = lcg.range(7, 100);
+            let held_time = lcg.range(1, time);
+            (time, lcg.next(held_time * (time - held_time)))
+        })
+        .collect::<Vec<_>>();
+
+    let row = |values: Vec<u64>| values.iter().map(|value| format!("{:>5}", value)).collect::<String>();
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        row(races.iter().map(|(time, _)| *time).collect()),
+        row(races.iter().map(|(_, distance)| *distance).collect()),
+    )
+}
+
+fn random_camel_cards(lcg: &mut Lcg, size: usize) -> String {
+    let symbols = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+
+    (0..size)
+        .map(|_| {
+            let hand = (0..5).map(|_| *lcg.pick(&symbols)).collect::<String>();
+            format!("{} {}\n", hand, lcg.range(1, 1001))
+        })
+        .collect()
+}
+
+// `count` distinct names ending with neither A nor Z, in a random order. They
+// have three letters unless more names are needed.
+fn node_names(lcg: &mut Lcg, count: usize) -> Vec<String> {
+    let mut length = 3;
+    while 26usize.pow(length - 1) * 24 < count {
+        length += 1;
+    }
+
+    let mut names = (0..26usize.pow(length - 1) * 24)
+        .map(|mut index| {
+            let mut name = vec![b'B' + (index % 24) as u8];
+            index /= 24;
+            for _ in 1..length {
+                name.push(b'A' + (index % 26) as u8);
+                index /= 26;
+            }
+            name.reverse();
+            String::from_utf8(name).unwrap()
+        })
+        .collect::<Vec<_>>();
+    lcg.shuffle(&mut names);
+    names.truncate(count);
+    names
+}
+
+// Every ghost walks a cycle from its A node back to its A node's successor
+// through its Z node, the length of every cycle being a multiple of the number
+// of turns so that the turn taken at every node is always the same. The other
+// turn leads to a random node, which never gets visited.
+fn random_network(lcg: &mut Lcg, size: usize) -> String {
+    const PRIMES: [usize; 10] = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31];
+
+    let mut primes = PRIMES;
+    lcg.shuffle(&mut primes);
+    let cycles = &primes[..6];
+    let number_of_turns = (size / cycles.iter().sum::<usize>()).max(1);
+    let turns = (0..number_of_turns).map(|_| *lcg.pick(&['L', 'R'])).collect::<String>();
+
+    let letters = (b'A'..=b'Z').map(|c| c as char).collect::<Vec<_>>();
+    let mut prefixes = letters.iter().flat_map(|&a| letters.iter().map(move |&b| format!("{}{}", a, b))).collect::<Vec<_>>();
+    prefixes.retain(|prefix| prefix != "AA");
+    lcg.shuffle(&mut prefixes);
+    let mut prefixes = prefixes.into_iter();
+    let mut names = node_names(lcg, cycles.iter().sum::<usize>() * number_of_turns).into_iter();
+    let mut nodes = Vec::new();
+    for (ghost, &cycle) in cycles.iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => {
+                let prefix = prefixes.next().unwrap();
+                (format!("{}A", prefix), format!("{}Z", prefix))
+            }
+        };
+        let path = std::iter::once(start)
+            .chain(names.by_ref().take(cycle * number_of_turns - 1))
+            .chain(std::iter::once(end))
+            .collect::<Vec<_>>();
+
+        for (step, name) in path.iter().enumerate() {
+            let next = path.get(step + 1).unwrap_or(&path[1]).clone();
+            nodes.push((name.clone(), next, step % number_of_turns));
+        }
+    }
+
+    let turns_bytes = turns.as_bytes();
+    let mut lines = nodes
+        .iter()
+        .map(|(name, next, step)| {
+            let other = &lcg.pick(&nodes).0;
+            match turns_bytes[*step] {
+                b'L' => format!("{} = ({}, {})\n", name, next, other),
+                _ => format!("{} = ({}, {})\n", name, other, next),
+            }
+        })
+        .collect::<Vec<_>>();
+    lcg.shuffle(&mut lines);
+
+    format!("{}\n\n{}", turns, lines.concat())
+}
+
+// Polynomial sequences of 21 values, of degree at most 5.
+fn random_oasis_report(lcg: &mut Lcg, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let coefficients = (0..=lcg.next(6)).map(|_| lcg.next(21) as i64 - 10).collect::<Vec<_>>();
+            let values = (0..21i64)
+                .map(|x| coefficients.iter().rev().fold(0, |value, c| value * x + c))
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>();
+            format!("{}\n", values.join(" "))
+        })
+        .collect()
+}
+
+// A loop around a random polyomino whose columns of cells each overlap the
+// previous one, which keeps its boundary a single closed path. Cells off the
+// loop get random pipes, except next to the animal, whose own pipe could
+// otherwise no longer be told apart.
+fn random_pipe_maze(lcg: &mut Lcg, size: usize) -> String {
+    let coarse = (size.max(5) - 3) / 2;
+
+    let mut intervals: Vec<(usize, usize)> = Vec::new();
+    for _ in 0..coarse {
+        let (a, b) = (lcg.next(coarse as u64) as usize, lcg.next(coarse as u64) as usize);
+        let (mut top, mut bottom) = (a.min(b), a.max(b));
+        if let Some(&(previous_top, previous_bottom)) = intervals.last() {
+            bottom = bottom.max(previous_top);
+            top = top.min(previous_bottom);
+        }
+        intervals.push((top, bottom));
+    }
+
+    // Coarse cell (i, j) covers the 3x3 block of cells around (2i + 2, 2j + 2).
+    let (height, width) = (2 * coarse + 3, 2 * coarse + 3);
+    let mut covered = vec![vec![false; width]; height];
+    for (j, &(top, bottom)) in intervals.iter().enumerate() {
+        for i in top..=bottom {
+            for row in &mut covered[2 * i + 1..2 * i + 4] {
+                row[2 * j + 1..2 * j + 4].fill(true);
+            }
+        }
+    }
+
+    let is_loop = |(i, j): (usize, usize)| {
+        covered[i][j] && (i - 1..=i + 1).any(|k| (j - 1..=j + 1).any(|l| !covered[k][l]))
+    };
+    let neighbors = |(i, j): (usize, usize)| [(i - 1, j), (i + 1, j), (i, j - 1), (i, j + 1)];
+
+    let start = (1..height).flat_map(|i| (1..width).map(move |j| (i, j))).find(|&p| is_loop(p)).unwrap();
+    let mut polygon = vec![start];
+    let mut current = *neighbors(start).iter().find(|&&p| is_loop(p)).unwrap();
+    while current != start {
+        let previous = polygon[polygon.len() - 1];
+        polygon.push(current);
+        current = *neighbors(current).iter().find(|&&p| p != previous && is_loop(p)).unwrap();
+    }
+
+    let mut rows = vec![vec!['.'; width]; height];
+    for row in rows.iter_mut() {
+        for c in row.iter_mut() {
+            *c = *lcg.pick(&['.', '.', '.', '|', '-', 'L', 'J', '7', 'F']);
+        }
+    }
+    for (k, &(i, j)) in polygon.iter().enumerate() {
+        let ends = [polygon[(k + polygon.len() - 1) % polygon.len()], polygon[(k + 1) % polygon.len()]];
+        let [north, south, west, _] = neighbors((i, j)).map(|p| ends.contains(&p));
+
+        rows[i][j] = match (north, south, west) {
+            (true, true, _) => '|',
+            (true, false, true) => 'J',
+            (true, false, false) => 'L',
+            (false, true, true) => '7',
+            (false, true, false) => 'F',
+            (false, false, _) => '-',
+        };
+    }
+
+    let (ai, aj) = *lcg.pick(&polygon);
+    for (i, j) in neighbors((ai, aj)) {
+        if !polygon.contains(&(i, j)) {
+            rows[i][j] = '.';
+        }
+    }
+    rows[ai][aj] = 'S';
+
+    rows.into_iter().map(|row| format!("{}\n", row.into_iter().collect::<String>())).collect()
+}
+
+// A random input for `day` of about `size` lines, or rows for the grids, the
+// same for the same seed. None for unknown days.
+pub fn random(day: u8, size: usize, seed: u64) -> Option<String> {
+    let size = size.max(1);
+    let mut lcg = Lcg(seed);
+
+    let input = match day {
+        1 => random_calibration_document(&mut lcg, size),
+        2 => random_games(&mut lcg, size),
+        3 => random_engine_schematic(&mut lcg, size),
+        4 => random_scratchcards(&mut lcg, size),
+        5 => random_almanac(&mut lcg, size),
+        6 => random_races(&mut lcg, size),
+        7 => random_camel_cards(&mut lcg, size),
+        8 => random_network(&mut lcg, size),
+        9 => random_oasis_report(&mut lcg, size),
+        10 => random_pipe_maze(&mut lcg, size),
+        _ => return None,
+    };
+
+    Some(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+    use aoc_core::Answer;
+
+    #[test]
+    fn test_every_day_solves_its_synthetic_input() {
+        // Freshly added days have no generator yet.
+        for day in registry::DAYS.iter() {
+            for scale in [1, 3] {
+                let Some(input) = input(day.number, scale) else {
+                    continue;
+                };
+                let mut solution = day.parse(&input).unwrap();
+
+                solution.part1();
+                solution.part2();
+            }
+        }
+    }
+
+    #[test]
+    fn test_synthetic_answers() {
+        let solve = |day: u8, part: u8| registry::find(day).unwrap().solve(&input(day, 1).unwrap(), part).unwrap();
+
+        assert_eq!(Answer::Number(281), solve(1, 2));
+        assert_eq!(Answer::Number(15), solve(4, 2));
+        assert_eq!(Answer::Number(13), solve(8, 1));
+        assert_eq!(Answer::Number(13 * 17 * 19 * 23 * 29 * 31), solve(8, 2));
+        assert_eq!(Answer::Number(36), solve(10, 2));
+    }
+
+    #[test]
+    fn test_every_day_solves_its_random_inputs() {
+        for day in registry::DAYS.iter() {
+            for (size, seed) in [(1, 0), (5, 1), (20, 2), (140, 3), (140, 4)] {
+                let Some(input) = random(day.number, size, seed) else {
+                    continue;
+                };
+                let mut solution = day.parse(&input).unwrap_or_else(|err| panic!("day {} seed {}: {}", day.number, seed, err));
+
+                solution.part1();
+                solution.part2();
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_inputs_hold_every_assumption() {
+        for day in registry::DAYS.iter() {
+            for (size, seed) in [(1, 0), (20, 1), (140, 2)] {
+                let Some(Ok(checks)) = random(day.number, size, seed).and_then(|input| day.lint(&input)) else {
+                    continue;
+                };
+                for check in checks {
+                    assert!(check.holds(), "day {} seed {}: {}", day.number, seed, check);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_is_reproducible() {
+        for day in 1..=10 {
+            assert_eq!(random(day, 50, 7), random(day, 50, 7));
+            assert_ne!(random(day, 50, 7), random(day, 50, 8));
+        }
+    }
+
+    #[test]
+    fn test_random_network_cycles() {
+        // The ghosts' cycles are 6 distinct primes times the number of turns.
+        let input = random(8, 1000, 11).unwrap();
+        let turns = input.lines().next().unwrap().len() as u64;
+        let Answer::Number(steps) = registry::find(8).unwrap().solve(&input, 2).unwrap() else {
+            panic!("day 8 part 2 is unsolved");
+        };
+
+        let steps = steps as u64;
+        assert!(steps.is_multiple_of(turns));
+        assert_eq!(6, [3, 5, 7, 11, 13, 17, 19, 23, 29, 31].iter().filter(|&&p| (steps / turns).is_multiple_of(p)).count());
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert_eq!(None, input(26, 1));
+        assert_eq!(None, random(26, 1, 0));
+    }
+}
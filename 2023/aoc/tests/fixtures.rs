@@ -0,0 +1,50 @@
+// Runs every day on the examples in tests/fixtures/<day>/<name>.txt and checks
+// the answers against <name>.expected. Adding a regression case only takes
+// dropping in a new pair of files.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use aoc::{fixtures, registry};
+use aoc_core::Answer;
+
+#[test]
+fn test_fixtures() {
+    let fixtures = fixtures::discover(&fixtures::default_dir()).unwrap();
+    assert!(!fixtures.is_empty(), "no fixtures found in {}", fixtures::default_dir().display());
+
+    let mut failures = Vec::new();
+
+    for fixture in &fixtures {
+        let id = format!("day {} {}", fixture.day, fixture.name);
+
+        let Some(day) = registry::find(fixture.day) else {
+            failures.push(format!("{}: day {} is not implemented", id, fixture.day));
+            continue;
+        };
+        let mut solution = match day.parse(&fixture.input) {
+            Ok(solution) => solution,
+            Err(err) => {
+                failures.push(format!("{}: {}", id, err));
+                continue;
+            }
+        };
+
+        for (part, expected) in &fixture.expected {
+            // A panicking solver fails its fixture without hiding the others.
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| solution.solve(*part)));
+
+            match answer {
+                // An expected answer for a part the day leaves unsolved fails the fixture.
+                Ok(Answer::Unsolved) => failures.push(format!("{} part {}: expected {} but the part is unsolved", id, part, expected)),
+                Ok(answer) if answer.to_string() == *expected => println!("{} part {}: ok", id, part),
+                Ok(answer) => failures.push(format!("{} part {}: expected {} but got {}", id, part, expected, answer)),
+                Err(_) => failures.push(format!("{} part {}: panicked", id, part)),
+            }
+        }
+    }
+
+    for failure in &failures {
+        println!("{}: FAILED", failure);
+    }
+    assert!(failures.is_empty(), "{} of the fixture answers are wrong:\n{}", failures.len(), failures.join("\n"));
+}
@@ -0,0 +1,129 @@
+use std::fmt;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// Environment variable overriding the directory in which `<day>/input.txt` is looked up.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Lookup { dir: PathBuf, day: u8 },
+}
+
+#[derive(Debug)]
+pub enum Error {
+    NotFound(PathBuf),
+    Io(PathBuf, io::Error),
+    Stdin(io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::NotFound(path) => write!(
+                f,
+                "input file {} not found, save the puzzle input there or pass a path (or - for stdin)",
+                path.display(),
+            ),
+            Error::Io(path, err) => write!(f, "could not read {}: {}", path.display(), err),
+            Error::Stdin(err) => write!(f, "could not read stdin: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Resolved when run rather than built, so that an installed binary does not
+// look in the source tree it was built from.
+pub fn default_dir() -> PathBuf {
+    match std::env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from("."),
+    }
+}
+
+pub fn lookup_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(day.to_string()).join("input.txt")
+}
+
+impl Source {
+    // `-` means stdin, any other argument is a path and no argument at all
+    // means the day's input.txt in the default lookup directory.
+    pub fn from_arg(arg: Option<&str>, day: u8) -> Self {
+        match arg {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::File(PathBuf::from(path)),
+            None => Source::Lookup { dir: default_dir(), day },
+        }
+    }
+
+    pub fn read(&self) -> Result<String, Error> {
+        match self {
+            Source::File(path) => read_file(path),
+            Source::Lookup { dir, day } => read_file(&lookup_path(dir, *day)),
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input).map_err(Error::Stdin)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+fn read_file(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|err| match err.kind() {
+        io::ErrorKind::NotFound => Error::NotFound(path.to_path_buf()),
+        _ => Error::Io(path.to_path_buf(), err),
+    })
+}
+
+// Reads the input named by the first command line argument, exiting with
+// a diagnostic when it cannot be read.
+pub fn from_args_or_exit(day: u8) -> String {
+    let arg = std::env::args().nth(1);
+
+    match Source::from_arg(arg.as_deref(), day).read() {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_source_from_arg() {
+        assert_eq!(Source::Stdin, Source::from_arg(Some("-"), 5));
+        assert_eq!(Source::File(PathBuf::from("other.txt")), Source::from_arg(Some("other.txt"), 5));
+        assert_eq!(Source::Lookup { dir: default_dir(), day: 5 }, Source::from_arg(None, 5));
+    }
+
+    #[test]
+    fn test_lookup_path() {
+        assert_eq!(PathBuf::from("inputs/7/input.txt"), lookup_path(Path::new("inputs"), 7));
+    }
+
+    #[test]
+    fn test_source_read() {
+        let dir = std::env::temp_dir().join(format!("aoc-core-input-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("3")).unwrap();
+        std::fs::write(dir.join("3").join("input.txt"), "467..114..").unwrap();
+
+        let source = Source::Lookup { dir: dir.clone(), day: 3 };
+        assert_eq!("467..114..", source.read().unwrap());
+
+        let source = Source::Lookup { dir: dir.clone(), day: 4 };
+        match source.read() {
+            Err(Error::NotFound(path)) => assert_eq!(lookup_path(&dir, 4), path),
+            _ => panic!("expected a missing input error"),
+        }
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
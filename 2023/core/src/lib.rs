@@ -0,0 +1,9 @@
+pub mod input;
+pub mod lint;
+pub mod math;
+pub mod parse;
+pub mod render;
+pub mod solution;
+
+pub use parse::ParseError;
+pub use solution::{Answer, Day, Solution};
@@ -0,0 +1,49 @@
+use std::fmt;
+
+// A property of the input a solution relies on without checking it, along
+// with what breaks it in the input at hand, if anything does.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub assumption: &'static str,
+    pub violation: Option<String>,
+}
+
+impl Check {
+    pub fn new(assumption: &'static str, violation: Option<String>) -> Self {
+        Self { assumption, violation }
+    }
+
+    pub fn holds(&self) -> bool {
+        self.violation.is_none()
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.violation {
+            None => write!(f, "ok      {}", self.assumption),
+            Some(violation) => write!(f, "FAILED  {}: {}", self.assumption, violation),
+        }
+    }
+}
+
+// A solution whose shortcuts only work for some inputs.
+pub trait Lint {
+    fn lint(&self) -> Vec<Check>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_display() {
+        let check = Check::new("card ids are 1, 2, 3, ...", None);
+        assert!(check.holds());
+        assert_eq!("ok      card ids are 1, 2, 3, ...", check.to_string());
+
+        let check = Check::new("card ids are 1, 2, 3, ...", Some("card 2 has id 5".into()));
+        assert!(!check.holds());
+        assert_eq!("FAILED  card ids are 1, 2, 3, ...: card 2 has id 5", check.to_string());
+    }
+}
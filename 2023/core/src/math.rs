@@ -0,0 +1,211 @@
+pub fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Overflows like `a * b` would, see checked_lcm for a non panicking version.
+pub fn lcm(a: u64, b: u64) -> u64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    a / gcd(a, b) * b
+}
+
+pub fn checked_lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+// LCM of all `values`, None when it does not fit in a u64.
+// The LCM of no values is 1.
+pub fn checked_lcm_of(values: &[u64]) -> Option<u64> {
+    values
+        .iter()
+        .try_fold(1, |lcm, &value| checked_lcm(lcm, value))
+}
+
+// Like checked_lcm_of but accumulating in a u128, for values whose
+// LCM only overflows a u64.
+pub fn wide_lcm_of(values: &[u64]) -> Option<u128> {
+    values.iter().try_fold(1u128, |lcm, &value| {
+        let value = value as u128;
+        if lcm == 0 || value == 0 {
+            return Some(0);
+        }
+
+        (lcm / gcd_u128(lcm, value)).checked_mul(value)
+    })
+}
+
+fn gcd_u128(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd_u128(b, a % b)
+    }
+}
+
+// Returns (g, x, y) such that a * x + b * y = g = gcd(a, b), with g >= 0.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// The x in [0, m) with a * x = 1 (mod m), if a and m are coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    assert!(m > 0, "Modulus has to be positive, got {}", m);
+
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g != 1 {
+        return None;
+    }
+
+    Some(x.rem_euclid(m))
+}
+
+/*
+Generalized Chinese Remainder Theorem: given congruences x = r_i (mod m_i), the moduli
+not necessarily being pairwise coprime, returns (x, lcm(m_i)) with x the smallest
+non-negative solution. None when the congruences are inconsistent or the LCM overflows.
+
+Two congruences x = r1 (mod m1) and x = r2 (mod m2) are merged by solving
+r1 + m1 * k = r2 (mod m2). With g = gcd(m1, m2) this only has a solution when g divides
+r2 - r1, in which case k = (r2 - r1) / g * inverse(m1 / g) (mod m2 / g).
+ */
+pub fn crt(congruences: &[(i128, i128)]) -> Option<(i128, i128)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+
+    for &(r2, m2) in congruences {
+        assert!(m2 > 0, "Modulus has to be positive, got {}", m2);
+
+        let r2 = r2.rem_euclid(m2);
+        let (g, p, _) = extended_gcd(m, m2);
+        let diff = r2 - x;
+        if diff % g != 0 {
+            return None;
+        }
+
+        let m2_g = m2 / g;
+        let k = (diff / g).rem_euclid(m2_g).checked_mul(p.rem_euclid(m2_g))?.rem_euclid(m2_g);
+        let lcm = m.checked_mul(m2_g)?;
+
+        x = x.checked_add(m.checked_mul(k)?)?.rem_euclid(lcm);
+        m = lcm;
+    }
+
+    Some((x, m))
+}
+
+// floor(sqrt(n)), computed exactly with Newton's method.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // Start above the root so that the iterations decrease monotonically to it.
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(1, gcd(7, 9));
+        assert_eq!(5, gcd(5, 0));
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(36, lcm(12, 18));
+        assert_eq!(6, lcm(2, 3));
+        assert_eq!(0, lcm(0, 3));
+    }
+
+    #[test]
+    fn test_checked_lcm_of() {
+        assert_eq!(Some(1), checked_lcm_of(&[]));
+        assert_eq!(Some(60), checked_lcm_of(&[4, 6, 10, 15]));
+        assert_eq!(None, checked_lcm_of(&[u64::MAX, u64::MAX - 1]));
+    }
+
+    #[test]
+    fn test_wide_lcm_of() {
+        assert_eq!(Some(60), wide_lcm_of(&[4, 6, 10, 15]));
+        assert_eq!(
+            Some(u64::MAX as u128 * (u64::MAX - 1) as u128),
+            wide_lcm_of(&[u64::MAX, u64::MAX - 1]),
+        );
+        assert_eq!(None, wide_lcm_of(&[u64::MAX, u64::MAX - 1, u64::MAX - 2, u64::MAX - 4]));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(2, g);
+        assert_eq!(2, 240 * x + 46 * y);
+
+        let (g, x, y) = extended_gcd(-12, 18);
+        assert_eq!(6, g);
+        assert_eq!(6, -12 * x + 18 * y);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(Some(7), mod_inverse(-3, 11));
+        assert_eq!(None, mod_inverse(6, 9));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        // Non coprime moduli.
+        assert_eq!(Some((10, 12)), crt(&[(4, 6), (2, 4)]));
+        assert_eq!(None, crt(&[(1, 6), (2, 4)]));
+        assert_eq!(Some((0, 1)), crt(&[]));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000u128 {
+            let root = isqrt(n);
+            assert!(root * root <= n && (root + 1) * (root + 1) > n, "isqrt({}) = {}", n, root);
+        }
+
+        assert_eq!(u64::MAX as u128, isqrt(u128::MAX));
+        assert_eq!(1 << 32, isqrt(1 << 64));
+        assert_eq!((1 << 32) - 1, isqrt((1 << 64) - 1));
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+// A parse failure of kind `K` at a 1-based line and column of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<K> {
+    pub line: usize,
+    pub column: usize,
+    pub kind: K,
+}
+
+impl<K> ParseError<K> {
+    pub fn new(line: usize, column: usize, kind: K) -> Self {
+        Self { line, column, kind }
+    }
+
+    // Errors of a single line parser are reported on line 1, this moves
+    // them to the line of the whole input they were found on.
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for ParseError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display> std::error::Error for ParseError<K> {}
+
+// 1-based column of `token` within `line`, `token` has to be a slice of `line`.
+pub fn column(line: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    debug_assert!(offset <= line.len(), "token is not a slice of line");
+
+    line[..offset].chars().count() + 1
+}
+
+// Parses every line with `parse`, reporting errors on the line they were found on.
+pub fn lines<T, K>(input: &str, parse: impl Fn(&str) -> Result<T, ParseError<K>>) -> Result<Vec<T>, ParseError<K>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|err| err.on_line(i + 1)))
+        .collect()
+}
+
+// Unwraps the parsed input of a day, exiting with a diagnostic when it is malformed.
+pub fn or_exit<T, E: fmt::Display>(day: u8, result: Result<T, E>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("day {}: {}", day, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = "Game 12: 3 blue";
+
+        assert_eq!(1, column(line, &line[..4]));
+        assert_eq!(6, column(line, line.split_whitespace().nth(1).unwrap()));
+        assert_eq!(16, column(line, &line[line.len()..]));
+    }
+
+    #[test]
+    fn test_lines() {
+        let parse = |line: &str| match line.parse::<u32>() {
+            Ok(n) => Ok(n),
+            Err(_) => Err(ParseError::new(1, 1, "not a number")),
+        };
+
+        assert_eq!(Ok(vec![1, 2, 3]), lines("1\n2\n3", parse));
+        assert_eq!(Err(ParseError::new(2, 1, "not a number")), lines("1\nx\n3", parse));
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        let err = ParseError::new(3, 14, "invalid color yellow");
+
+        assert_eq!("line 3, column 14: invalid color yellow", err.to_string());
+    }
+}
@@ -0,0 +1,190 @@
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    fn foreground_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::Gray => 90,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub color: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style { color: None, bold: false, dim: false };
+
+    pub const fn color(color: Color) -> Self {
+        Style { color: Some(color), bold: false, dim: false }
+    }
+
+    pub const fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    pub const fn dim(self) -> Self {
+        Style { dim: true, ..self }
+    }
+
+    // The SGR escape sequence switching from any style to this one.
+    fn escape(&self) -> String {
+        let mut codes = vec!["0".to_string()];
+        if self.bold {
+            codes.push("1".into());
+        }
+        if self.dim {
+            codes.push("2".into());
+        }
+        if let Some(color) = self.color {
+            codes.push(color.foreground_code().to_string());
+        }
+
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+// A picture of a puzzle as rows of styled characters, with a caption below.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    width: usize,
+    cells: Vec<(char, Style)>,
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, cells: vec![(' ', Style::PLAIN); width * height], caption: String::new() }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn set(&mut self, (i, j): (usize, usize), c: char, style: Style) {
+        self.cells[i * self.width + j] = (c, style);
+    }
+
+    pub fn get(&self, (i, j): (usize, usize)) -> (char, Style) {
+        self.cells[i * self.width + j]
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[(char, Style)]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    // The characters alone, for terminals without colors and for tests.
+    pub fn to_plain(&self) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            text.extend(row.iter().map(|&(c, _)| c));
+            text.push('\n');
+        }
+        if !self.caption.is_empty() {
+            writeln!(text, "{}", self.caption).unwrap();
+        }
+
+        text
+    }
+
+    // The characters with ANSI escape sequences for their styles, every line
+    // ending with the default style.
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+        for row in self.rows() {
+            let mut current = Style::PLAIN;
+            for &(c, style) in row {
+                if style != current {
+                    text.push_str(&style.escape());
+                    current = style;
+                }
+                text.push(c);
+            }
+            if current != Style::PLAIN {
+                text.push_str("\x1b[0m");
+            }
+            text.push('\n');
+        }
+        if !self.caption.is_empty() {
+            writeln!(text, "{}", self.caption).unwrap();
+        }
+
+        text
+    }
+}
+
+// A solution that can draw itself. `show` gets called with every step of the
+// animation, if there is one, the last frame being the full picture.
+pub trait Render {
+    fn render(&mut self, show: &mut dyn FnMut(&Frame));
+}
+
+// Escape sequence clearing the terminal and moving the cursor to its top left
+// corner, to draw every frame of an animation over the previous one.
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> Frame {
+        let mut frame = Frame::new(3, 2);
+        frame.set((0, 0), '1', Style::color(Color::Green).bold());
+        frame.set((0, 1), '2', Style::color(Color::Green).bold());
+        frame.set((0, 2), '.', Style::PLAIN);
+        frame.set((1, 0), '*', Style::color(Color::Yellow));
+        frame.set((1, 1), '.', Style::color(Color::Gray).dim());
+        frame.set((1, 2), '#', Style::color(Color::Gray).dim());
+        frame
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = frame();
+
+        assert_eq!((3, 2), (frame.width(), frame.height()));
+        assert_eq!(('*', Style::color(Color::Yellow)), frame.get((1, 0)));
+        assert_eq!(0, Frame::new(0, 0).height());
+    }
+
+    #[test]
+    fn test_to_plain() {
+        let mut frame = frame();
+        assert_eq!("12.\n*.#\n", frame.to_plain());
+
+        frame.caption = "Sum: 12".into();
+        assert_eq!("12.\n*.#\nSum: 12\n", frame.to_plain());
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        assert_eq!(
+            "\x1b[0;1;32m12\x1b[0m.\n\x1b[0;33m*\x1b[0;2;90m.#\x1b[0m\n",
+            frame().to_ansi(),
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::lint::{Check, Lint};
+use crate::render::{Frame, Render};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    // The part has not been solved for this day yet.
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+impl_answer_from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+// A day's puzzle: the input is parsed once and both parts are solved from the parsed value.
+pub trait Solution {
+    type Error: std::error::Error + 'static where Self: Sized;
+
+    fn parse(input: &str) -> Result<Self, Self::Error> where Self: Sized;
+
+    fn part1(&mut self) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(&mut self) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn solve(&mut self, part: u8) -> Answer {
+        match part {
+            1 => self.part1(),
+            2 => self.part2(),
+            _ => panic!("Invalid part: {}", part),
+        }
+    }
+}
+
+type ParseResult = Result<Box<dyn Solution>, Box<dyn Error>>;
+type RenderFn = fn(&str, &mut dyn FnMut(&Frame)) -> Result<(), Box<dyn Error>>;
+type LintFn = fn(&str) -> Result<Vec<Check>, Box<dyn Error>>;
+
+// An entry of a day registry, hiding the concrete Solution type
+// so that days can be iterated over generically.
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    parse: fn(&str) -> ParseResult,
+    render: Option<RenderFn>,
+    lint: Option<LintFn>,
+}
+
+fn parse_boxed<T: Solution + 'static>(input: &str) -> ParseResult {
+    match T::parse(input) {
+        Ok(solution) => Ok(Box::new(solution)),
+        Err(err) => Err(Box::new(err)),
+    }
+}
+
+fn render_parsed<T: Solution + Render + 'static>(input: &str, show: &mut dyn FnMut(&Frame)) -> Result<(), Box<dyn Error>> {
+    T::parse(input)?.render(show);
+    Ok(())
+}
+
+fn lint_parsed<T: Solution + Lint + 'static>(input: &str) -> Result<Vec<Check>, Box<dyn Error>> {
+    Ok(T::parse(input)?.lint())
+}
+
+impl Day {
+    pub const fn new<T: Solution + 'static>(number: u8, title: &'static str) -> Self {
+        Self { number, title, parse: parse_boxed::<T>, render: None, lint: None }
+    }
+
+    // Lets the day be drawn, `T` being its Solution type.
+    pub const fn with_render<T: Solution + Render + 'static>(self) -> Self {
+        Self { render: Some(render_parsed::<T>), ..self }
+    }
+
+    // Lets the assumptions the day makes about its input be checked.
+    pub const fn with_lint<T: Solution + Lint + 'static>(self) -> Self {
+        Self { lint: Some(lint_parsed::<T>), ..self }
+    }
+
+    pub fn parse(&self, input: &str) -> ParseResult {
+        (self.parse)(input)
+    }
+
+    pub fn solve(&self, input: &str, part: u8) -> Result<Answer, Box<dyn Error>> {
+        Ok(self.parse(input)?.solve(part))
+    }
+
+    // None when the day cannot be drawn.
+    pub fn render(&self, input: &str, show: &mut dyn FnMut(&Frame)) -> Option<Result<(), Box<dyn Error>>> {
+        self.render.map(|render| render(input, show))
+    }
+
+    // None when the day makes no assumptions worth checking.
+    pub fn lint(&self, input: &str) -> Option<Result<Vec<Check>, Box<dyn Error>>> {
+        self.lint.map(|lint| lint(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Style;
+
+    struct Sum(Vec<u32>);
+
+    impl Solution for Sum {
+        type Error = std::num::ParseIntError;
+
+        fn parse(input: &str) -> Result<Self, Self::Error> {
+            let numbers = input
+                .split_whitespace()
+                .map(|n| n.parse())
+                .collect::<Result<_, _>>()?;
+
+            Ok(Sum(numbers))
+        }
+
+        fn part1(&mut self) -> Answer {
+            self.0.iter().sum::<u32>().into()
+        }
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("-3", Answer::from(-3isize).to_string());
+        assert_eq!("5905", Answer::from(5905u32).to_string());
+        assert_eq!("AAA", Answer::from("AAA").to_string());
+        assert_eq!("unsolved", Answer::Unsolved.to_string());
+    }
+
+    impl Render for Sum {
+        fn render(&mut self, show: &mut dyn FnMut(&Frame)) {
+            let mut frame = Frame::new(self.0.len(), 1);
+            for (j, n) in self.0.iter().enumerate() {
+                frame.set((0, j), char::from_digit(*n, 10).unwrap_or('+'), Style::PLAIN);
+                show(&frame);
+            }
+        }
+    }
+
+    #[test]
+    fn test_day_render() {
+        let mut frames = Vec::new();
+        let day = Day::new::<Sum>(1, "Sum").with_render::<Sum>();
+
+        assert!(day.render("1 2 3", &mut |frame| frames.push(frame.to_plain())).unwrap().is_ok());
+        assert_eq!(vec!["1  \n", "12 \n", "123\n"], frames);
+        assert!(day.render("1 x 3", &mut |_| {}).unwrap().is_err());
+        assert!(Day::new::<Sum>(1, "Sum").render("1 2 3", &mut |_| {}).is_none());
+    }
+
+    impl Lint for Sum {
+        fn lint(&self) -> Vec<Check> {
+            let violation = self.0.iter().position(|&n| n == 0).map(|i| format!("number {} is 0", i + 1));
+            vec![Check::new("numbers are positive", violation)]
+        }
+    }
+
+    #[test]
+    fn test_day_lint() {
+        let day = Day::new::<Sum>(1, "Sum").with_lint::<Sum>();
+
+        assert_eq!(vec![Check::new("numbers are positive", None)], day.lint("1 2 3").unwrap().unwrap());
+        assert_eq!(
+            vec![Check::new("numbers are positive", Some("number 2 is 0".into()))],
+            day.lint("1 0 3").unwrap().unwrap(),
+        );
+        assert!(day.lint("1 x 3").unwrap().is_err());
+        assert!(Day::new::<Sum>(1, "Sum").lint("1 2 3").is_none());
+    }
+
+    #[test]
+    fn test_day_solve() {
+        let day = Day::new::<Sum>(1, "Sum");
+
+        assert_eq!(Answer::Number(6), day.solve("1 2 3", 1).unwrap());
+        assert_eq!(Answer::Unsolved, day.solve("1 2 3", 2).unwrap());
+        assert!(day.solve("1 x 3", 1).is_err());
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use aoc_core::render::{Frame, Style};
+use aoc_core::ParseError;
+
+// (row, column), with (0, 0) in the top left corner.
+pub type Position = (usize, usize);
+
+// (row, column) offsets of the 4 orthogonal neighbors: east, south, north and west.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, 1), (1, 0), (-1, 0), (0, -1)];
+
+// (row, column) offsets of all 8 neighbors, clockwise starting at west.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [(0, -1), (-1, -1), (-1, 0), (-1, 1),
+                                               (0, 1), (1, 1), (1, 0), (1, -1)];
+
+// A row whose length differs from the length of the first row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaggedRow {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for RaggedRow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected a row of {} cells, found {}", self.expected, self.found)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "Grid of {}x{} needs {} cells", width, height, width * height);
+
+        Self { cells, width, height }
+    }
+
+    // Parses one cell per character, every line being a row. Errors of `parse_cell`
+    // and rows of different lengths are reported at their line and column.
+    pub fn parse<K: From<RaggedRow>>(
+        input: &str,
+        mut parse_cell: impl FnMut(Position, char) -> Result<T, K>,
+    ) -> Result<Self, ParseError<K>> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (i, line) in input.lines().enumerate() {
+            let mut row_width = 0;
+            for (j, c) in line.chars().enumerate() {
+                let cell = parse_cell((i, j), c).map_err(|kind| ParseError::new(i + 1, j + 1, kind))?;
+                cells.push(cell);
+                row_width += 1;
+            }
+
+            if i == 0 {
+                width = row_width;
+            } else if row_width != width {
+                let kind = RaggedRow { expected: width, found: row_width };
+                return Err(ParseError::new(i + 1, row_width.min(width) + 1, kind.into()));
+            }
+            height += 1;
+        }
+
+        Ok(Self { cells, width, height })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.0 < self.height && position.1 < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        if self.contains(position) {
+            Some(&self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            Some(&mut self.cells[position.0 * self.width + position.1])
+        } else {
+            None
+        }
+    }
+
+    // The position `offset` away from `position`, if it is inside the grid.
+    pub fn offset(&self, position: Position, offset: (isize, isize)) -> Option<Position> {
+        let i = position.0.checked_add_signed(offset.0)?;
+        let j = position.1.checked_add_signed(offset.1)?;
+
+        if self.contains((i, j)) {
+            Some((i, j))
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        DIRECTIONS_4
+            .iter()
+            .filter_map(move |&direction| self.offset(position, direction))
+    }
+
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        DIRECTIONS_8
+            .iter()
+            .filter_map(move |&direction| self.offset(position, direction))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |i| (0..width).map(move |j| (i, j)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(move |i| self.row(i))
+    }
+
+    pub fn column(&self, j: usize) -> impl Iterator<Item = &T> {
+        assert!(j < self.width, "Column {} out of bounds of a grid {} wide", j, self.width);
+
+        self.cells.iter().skip(j).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |j| self.column(j))
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    // Draws the grid one character per cell, one line per row.
+    pub fn render(&self, mut cell: impl FnMut(Position, &T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+
+        for (i, row) in self.rows().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                s.push(cell((i, j), value));
+            }
+            s.push('\n');
+        }
+
+        s
+    }
+
+    // Like `render`, with a style for every character.
+    pub fn frame(&self, mut cell: impl FnMut(Position, &T) -> (char, Style)) -> Frame {
+        let mut frame = Frame::new(self.width, self.height);
+        for (position, value) in self.iter() {
+            let (c, style) = cell(position, value);
+            frame.set(position, c, style);
+        }
+
+        frame
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("Position {:?} out of bounds of a {}x{} grid", position, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("Position {:?} out of bounds of a {}x{} grid", position, width, height))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for value in row {
+                write!(f, "{}", value)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum ErrorKind {
+        InvalidCharacter(char),
+        Ragged(RaggedRow),
+    }
+
+    impl From<RaggedRow> for ErrorKind {
+        fn from(ragged_row: RaggedRow) -> Self {
+            ErrorKind::Ragged(ragged_row)
+        }
+    }
+
+    fn parse_digits(input: &str) -> Result<Grid<u32>, ParseError<ErrorKind>> {
+        Grid::parse(input, |_, c| c.to_digit(10).ok_or(ErrorKind::InvalidCharacter(c)))
+    }
+
+    #[test]
+    fn test_grid_parse() {
+        let grid = parse_digits("123\n456").unwrap();
+
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!(1, grid[(0, 0)]);
+        assert_eq!(6, grid[(1, 2)]);
+        assert_eq!(None, grid.get((2, 0)));
+        assert_eq!(None, grid.get((0, 3)));
+    }
+
+    #[test]
+    fn test_grid_parse_errors() {
+        let err = parse_digits("123\n4x6").err();
+        assert_eq!(Some(ParseError::new(2, 2, ErrorKind::InvalidCharacter('x'))), err);
+
+        let err = parse_digits("123\n45").err();
+        assert_eq!(Some(ParseError::new(2, 3, ErrorKind::Ragged(RaggedRow { expected: 3, found: 2 }))), err);
+
+        let err = parse_digits("123\n4567").err();
+        assert_eq!(Some(ParseError::new(2, 4, ErrorKind::Ragged(RaggedRow { expected: 3, found: 4 }))), err);
+    }
+
+    #[test]
+    fn test_grid_neighbors() {
+        let grid = parse_digits("123\n456\n789").unwrap();
+
+        let neighbors = grid.neighbors4((0, 0)).map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(vec![2, 4], neighbors);
+
+        let neighbors = grid.neighbors8((0, 0)).map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(vec![2, 5, 4], neighbors);
+
+        let neighbors = grid.neighbors8((1, 1)).map(|p| grid[p]).collect::<Vec<_>>();
+        assert_eq!(vec![4, 1, 2, 3, 6, 9, 8, 7], neighbors);
+
+        assert_eq!(None, grid.offset((2, 2), (0, 1)));
+        assert_eq!(Some((1, 2)), grid.offset((2, 2), (-1, 0)));
+    }
+
+    #[test]
+    fn test_grid_rows_and_columns() {
+        let grid = parse_digits("123\n456").unwrap();
+
+        assert_eq!(vec![&[1, 2, 3][..], &[4, 5, 6][..]], grid.rows().collect::<Vec<_>>());
+        assert_eq!(vec![2, 5], grid.column(1).copied().collect::<Vec<_>>());
+        assert_eq!(
+            vec![vec![1, 4], vec![2, 5], vec![3, 6]],
+            grid.columns().map(|c| c.copied().collect::<Vec<_>>()).collect::<Vec<_>>(),
+        );
+        assert_eq!(vec![((0, 0), &1), ((0, 1), &2)], grid.iter().take(2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_grid_display_and_render() {
+        let grid = parse_digits("123\n456").unwrap();
+
+        assert_eq!("123\n456", grid.to_string());
+        assert_eq!("#.#\n.#.\n", grid.render(|_, &n| if n % 2 == 1 { '#' } else { '.' }));
+
+        let bold = Style::PLAIN.bold();
+        let frame = grid.frame(|(i, _), &n| (char::from_digit(n, 10).unwrap(), if i == 0 { bold } else { Style::PLAIN }));
+        assert_eq!("123\n456\n", frame.to_plain());
+        assert_eq!(('2', bold), frame.get((0, 1)));
+        assert_eq!(('6', Style::PLAIN), frame.get((1, 2)));
+    }
+
+    #[test]
+    fn test_grid_empty() {
+        let grid = parse_digits("").unwrap();
+
+        assert_eq!(0, grid.width());
+        assert_eq!(0, grid.height());
+        assert_eq!(0, grid.rows().count());
+        assert_eq!("", grid.render(|_, _| '.'));
+    }
+}